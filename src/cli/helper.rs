@@ -0,0 +1,240 @@
+use std::cell::RefCell;
+
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::Helper;
+
+/// Whether the brackets and strings in some source are balanced.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Balance {
+    Complete,
+    Incomplete,
+    Unexpected(char),
+}
+
+/// Rustyline helper for the REPL, which rejects lines that close a bracket
+/// that was never opened in the form that is being entered.
+///
+/// Unbalanced input is deliberately `Valid` rather than `Incomplete`: the REPL
+/// reads each continuation line with its own `readline` call instead. With
+/// `Incomplete`, rustyline continues the form in the same buffer, which shows
+/// no prompt on continuation lines, and Ctrl-C in it can't be told apart from
+/// Ctrl-C on an empty prompt, which exits. The lines read so far are passed in
+/// with `set_pending`, so a closing bracket is checked against the whole form.
+#[derive(Default)]
+pub struct ReplHelper {
+    pending: RefCell<String>,
+}
+
+impl ReplHelper {
+    /// Sets the lines of the current form that have already been entered.
+    pub fn set_pending(&self, pending: &str) {
+        pending.clone_into(&mut self.pending.borrow_mut());
+    }
+}
+
+impl Validator for ReplHelper {
+    fn validate(
+        &self,
+        ctx: &mut ValidationContext,
+    ) -> rustyline::Result<ValidationResult> {
+        let source = format!("{}{}", self.pending.borrow(), ctx.input());
+
+        let result = match check_balance(&source) {
+            Balance::Unexpected(c) => {
+                ValidationResult::Invalid(Some(format!(
+                    "  <- unexpected '{c}'"
+                )))
+            },
+            Balance::Complete | Balance::Incomplete => {
+                ValidationResult::Valid(None)
+            },
+        };
+
+        Ok(result)
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {
+}
+
+impl Helper for ReplHelper {
+}
+
+/// Checks whether all brackets and strings in `source` are closed.
+pub fn check_balance(source: &str) -> Balance {
+    let chars = source.chars().collect::<Vec<_>>();
+
+    // The closing brackets we expect, or '"' while inside an f-string.
+    let mut stack = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        index += 1;
+
+        if stack.last() == Some(&'"') {
+            match (c, next) {
+                ('\\', _) | ('{', Some('{')) | ('}', Some('}')) => index += 1,
+                ('"', _) => {
+                    stack.pop();
+                },
+                ('{', _) => stack.push('}'),
+                _ => (),
+            }
+
+            continue;
+        }
+
+        let is_prefix = index < 2 || !is_symbol_char(chars[index - 2]);
+
+        match (c, next) {
+            // A `#` after a symbol is part of it, like `tmp#` in a
+            // quasiquote.
+            ('#', _) if is_prefix => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            },
+            ('(', _) => stack.push(')'),
+            ('[', _) => stack.push(']'),
+            ('{', _) => stack.push('}'),
+            // Matching closing brackets are popped by the guard.
+            (')' | ']' | '}', _) if stack.pop() != Some(c) => {
+                return Balance::Unexpected(c);
+            },
+            ('"', _) => {
+                match skip_string(&chars, index) {
+                    Some(end) => index = end,
+                    None => return Balance::Incomplete,
+                }
+            },
+            ('f', Some('"')) if is_prefix => {
+                index += 1;
+                stack.push('"');
+            },
+            ('r', Some('"' | '#')) if is_prefix => {
+                match skip_raw_string(&chars, index) {
+                    Some(end) => index = end,
+                    None => return Balance::Incomplete,
+                }
+            },
+            _ => (),
+        }
+    }
+
+    if stack.is_empty() {
+        Balance::Complete
+    } else {
+        Balance::Incomplete
+    }
+}
+
+// Returns the index after the string that starts just before `start`.
+fn skip_string(chars: &[char], start: usize) -> Option<usize> {
+    let is_long = chars[start..].starts_with(&['"', '"']);
+    let quotes: &[char] = if is_long { &['"', '"', '"'] } else { &['"'] };
+
+    let mut index = if is_long { start + 2 } else { start };
+
+    while index < chars.len() {
+        if chars[index] == '\\' {
+            index += 2;
+        } else if chars[index..].starts_with(quotes) {
+            return Some(index + quotes.len());
+        } else {
+            index += 1;
+        }
+    }
+
+    None
+}
+
+// Returns the index after the raw string whose `r` is just before `start`.
+fn skip_raw_string(chars: &[char], start: usize) -> Option<usize> {
+    let hashes = chars[start..].iter().take_while(|c| **c == '#').count();
+
+    if chars.get(start + hashes) != Some(&'"') {
+        return Some(start);
+    }
+
+    let mut terminator = vec!['"'];
+    terminator.extend(std::iter::repeat('#').take(hashes));
+
+    let body = start + hashes + 1;
+
+    (body..chars.len())
+        .find(|index| chars[*index..].starts_with(&terminator))
+        .map(|index| index + terminator.len())
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!$%*+-.<?=>@^_".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced_brackets_are_complete() {
+        assert_eq!(check_balance("(+ 1 [2 {:a 3}])"), Balance::Complete);
+        assert_eq!(check_balance(""), Balance::Complete);
+    }
+
+    #[test]
+    fn unclosed_brackets_are_incomplete() {
+        assert_eq!(check_balance("(defn f [x]\n"), Balance::Incomplete);
+        assert_eq!(check_balance("{:a [1"), Balance::Incomplete);
+    }
+
+    #[test]
+    fn unexpected_closers() {
+        assert_eq!(check_balance(")"), Balance::Unexpected(')'));
+        assert_eq!(check_balance("(+ 1 2]"), Balance::Unexpected(']'));
+        assert_eq!(check_balance("[1 2})"), Balance::Unexpected('}'));
+    }
+
+    #[test]
+    fn brackets_in_strings_are_ignored() {
+        assert_eq!(check_balance(r#"(println ")]}")"#), Balance::Complete);
+        assert_eq!(check_balance(r#"(println "\"(")"#), Balance::Complete);
+        assert_eq!(check_balance(r##"r#"("#"##), Balance::Complete);
+        assert_eq!(check_balance("\"\"\"\n\"(\n\"\"\""), Balance::Complete);
+    }
+
+    #[test]
+    fn unclosed_strings_are_incomplete() {
+        assert_eq!(check_balance(r#"(println "abc"#), Balance::Incomplete);
+        assert_eq!(check_balance(r#"r#"abc""#), Balance::Incomplete);
+        assert_eq!(check_balance(r#"f"{x"#), Balance::Incomplete);
+    }
+
+    #[test]
+    fn fstring_values_are_checked() {
+        assert_eq!(check_balance(r#"f"{(+ 1 2)} {{(""#), Balance::Complete);
+        assert_eq!(check_balance(r#"f"{(+ 1 2]}""#), Balance::Unexpected(']'));
+    }
+
+    #[test]
+    fn brackets_in_comments_are_ignored() {
+        assert_eq!(check_balance("(+ 1 # )]\n 2)"), Balance::Complete);
+        assert_eq!(check_balance("# (\n"), Balance::Complete);
+    }
+
+    #[test]
+    fn gensym_symbols_are_not_comments() {
+        assert_eq!(check_balance("`(let [tmp# 1] tmp#)"), Balance::Complete);
+    }
+}
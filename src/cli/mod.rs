@@ -0,0 +1,134 @@
+mod helper;
+
+use std::path::Path;
+
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+
+use self::helper::{check_balance, Balance, ReplHelper};
+use crate::env::TAPR_PRELUDE;
+use crate::error::ErrorKind;
+use crate::interpreter::{Interpreter, Value};
+use crate::Result;
+
+// TODO More sophisticated command line handling.
+// TODO Save repl-history in a config dir
+// TODO Allow resetting of REPL history
+
+const HISTFILE: &str = "history.txt";
+
+pub fn main() {
+    let mut args = std::env::args();
+
+    let result = match args.len() {
+        1 => repl(),
+        2 => run_file(&args.nth(1).expect("args to have 2 elements.")),
+        _ => Err(ErrorKind::UsageError.into()),
+    };
+
+    if let Err(error) = result {
+        eprintln!("{error}");
+    }
+}
+
+fn repl() -> Result<()> {
+    println!("Welcome to Tapr {}.", env!("CARGO_PKG_VERSION"));
+
+    println!("Press Ctrl-C to cancel a form, or Ctrl-D to exit.");
+
+    let mut rl = Editor::<ReplHelper>::new().unwrap();
+    rl.set_helper(Some(ReplHelper::default()));
+
+    let _result = rl.load_history(HISTFILE);
+    let starting_index = std::fs::read_to_string(HISTFILE)
+        .map_or(1, |s| s.trim().lines().count());
+
+    let mut intp = new_interpreter()?;
+
+    for line_no in starting_index.. {
+        if eval_form(&mut rl, line_no, &mut intp)? {
+            break;
+        }
+    }
+
+    let _result = rl.save_history(HISTFILE);
+    println!("Exiting REPL...");
+
+    Ok(())
+}
+
+/// Reads and evaluates a form, which may span multiple lines. Returns whether
+/// the REPL should exit.
+fn eval_form(
+    rl: &mut Editor<ReplHelper>,
+    line_no: usize,
+    intp: &mut Interpreter,
+) -> Result<bool> {
+    let prompt = format!("[{line_no}]> ");
+    let continuation_prompt =
+        format!("{:>width$}", "...> ", width = prompt.len());
+
+    let mut source = String::new();
+
+    loop {
+        let helper = rl.helper().expect("REPL should have helper.");
+        helper.set_pending(&source);
+
+        let prompt =
+            if source.is_empty() { &prompt } else { &continuation_prompt };
+
+        match rl.readline(prompt) {
+            Ok(line) => {
+                source.push_str(&line);
+                source.push('\n');
+            },
+            // Ctrl-C cancels the current form, or exits if there is none.
+            Err(ReadlineError::Interrupted) if !source.is_empty() => {
+                return Ok(false)
+            },
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => {
+                return Ok(true)
+            },
+            Err(err) => return Err(err.into()),
+        }
+
+        if source.trim().is_empty() {
+            source.clear();
+        } else if check_balance(&source) != Balance::Incomplete {
+            break;
+        }
+    }
+
+    let source = source.trim();
+
+    rl.add_history_entry(source);
+
+    match run_code(source, intp, &format!("repl_{line_no}")) {
+        Ok(value) => println!("{}", value.repl_repr()),
+        Err(error) => eprintln!("{error}"),
+    }
+
+    Ok(false)
+}
+
+fn run_file(filename: &str) -> Result<()> {
+    let mut intp = new_interpreter()?;
+
+    intp.interpret_file(Path::new(filename))?;
+
+    Ok(())
+}
+
+fn new_interpreter() -> Result<Interpreter<'static>> {
+    let mut intp = Interpreter::default();
+
+    if *TAPR_PRELUDE {
+        intp.load_prelude()?;
+    }
+
+    Ok(intp)
+}
+
+fn run_code(source: &str, intp: &mut Interpreter, name: &str) -> Result<Value> {
+    intp.interpret(source, name)
+}
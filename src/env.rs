@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use once_cell::sync::Lazy;
 
 #[derive(Copy, Clone)]
@@ -25,3 +27,19 @@ pub(crate) static DEBUG_AST: Lazy<DebugAst> = Lazy::new(DebugAst::from_env);
 
 pub(crate) static DEBUG_PARSER: Lazy<bool> =
     Lazy::new(|| std::env::var("DEBUG_PARSER").is_ok());
+
+/// Whether the standard library is imported automatically.
+pub(crate) static TAPR_PRELUDE: Lazy<bool> =
+    Lazy::new(|| is_set_and_not_empty("TAPR_PRELUDE"));
+
+/// Directories searched for imported modules, after the directory of the
+/// importing file.
+pub(crate) static TAPR_PATH: Lazy<Vec<PathBuf>> = Lazy::new(|| {
+    std::env::var_os("TAPR_PATH")
+        .map(|paths| std::env::split_paths(&paths).collect())
+        .unwrap_or_default()
+});
+
+fn is_set_and_not_empty(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
@@ -1,18 +1,45 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
-use crate::interpreter::Value;
+use crate::interpreter::{Callable, Value};
 use crate::location::Location;
 use crate::parser::parameters::ParameterType;
+use crate::parser::pattern::Pattern;
 use crate::parser::Rule;
 
+// The amount of frames printed at either end of a long backtrace.
+const BACKTRACE_EDGE: usize = 10;
+
 #[derive(Debug)]
 pub struct Error {
     pub location: Option<Location>,
     pub kind: ErrorKind,
+    pub hint: Option<String>,
+    /// The calls that led to this error, innermost first.
+    pub backtrace: Vec<Frame>,
+}
+
+/// A call to a function, with the location it was called from.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub name: String,
+    pub location: Location,
 }
 impl Error {
     pub fn new(location: Location, kind: ErrorKind) -> Self {
-        Self { location: Some(location), kind }
+        Self {
+            location: Some(location),
+            kind,
+            hint: None,
+            backtrace: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_hint(mut self, hint: Option<String>) -> Self {
+        self.hint = hint;
+        self
     }
 
     pub fn has_location(&self) -> bool {
@@ -20,13 +47,112 @@ impl Error {
     }
 }
 
+impl ErrorKind {
+    /// Returns the name of this kind in kebab-case, e.g. `symbol-not-defined`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UsageError => "usage-error",
+            Self::ParserError { .. } => "parser-error",
+            Self::IntegerLiteralOutOfRange(_) => "integer-literal-out-of-range",
+            Self::IOError { .. } => "io-error",
+            Self::ReadLine { .. } => "read-line",
+            Self::ModuleNotDefined(_) => "module-not-defined",
+            Self::ModuleNotFound { .. } => "module-not-found",
+            Self::StdModuleNotFound(_) => "std-module-not-found",
+            Self::InvalidImport { .. } => "invalid-import",
+            Self::CircularImport(_) => "circular-import",
+            Self::SymbolNotDefined(_) => "symbol-not-defined",
+            Self::PrivateSymbol { .. } => "private-symbol",
+            Self::SymbolDefined(_) => "symbol-defined",
+            Self::NotCallable(_) => "not-callable",
+            Self::PatternMismatch { .. } => "pattern-mismatch",
+            Self::RecurNotInTailPosition => "recur-not-in-tail-position",
+            Self::Thrown(_) => "thrown",
+            Self::MaxCallDepth(_) => "max-call-depth",
+            Self::MacroNotExpanded(_) => "macro-not-expanded",
+            Self::UnquoteOutsideQuasiquote => "unquote-outside-quasiquote",
+            Self::UnquoteSplicingOutsideList => "unquote-splicing-outside-list",
+            Self::NotCode(_) => "not-code",
+            Self::NonLastParameterIsRest => "non-last-parameter-is-rest",
+            Self::RequiredParamAfterOptional => "required-param-after-optional",
+            Self::InvalidParameterType(_) => "invalid-parameter-type",
+            Self::InvalidArgument { .. } => "invalid-argument",
+            Self::InvalidConversion { .. } => "invalid-conversion",
+            Self::WrongAmountOfFixedArgs { .. } => "wrong-amount-of-fixed-args",
+            Self::WrongAmountOfMinArgs { .. } => "wrong-amount-of-min-args",
+            Self::WrongAmountOfMaxArgs { .. } => "wrong-amount-of-max-args",
+            Self::TailOnEmptyList => "tail-on-empty-list",
+            Self::InvalidBinOp { .. } => "invalid-bin-op",
+            Self::ParseNumberError(_) => "parse-number-error",
+            Self::IntegerOutOfRange(_) => "integer-out-of-range",
+            Self::IntegerOverflow { .. } => "integer-overflow",
+            Self::DivisionByZero(_) => "division-by-zero",
+            Self::NegativeInteger(_) => "negative-integer",
+            Self::InvalidFormatString { .. } => "invalid-format-string",
+            Self::FormatArgumentMissing(_) => "format-argument-missing",
+        }
+    }
+}
+
+impl Frame {
+    pub fn new(callable: &dyn Callable, location: Location) -> Self {
+        let name = callable.name().unwrap_or("<anonymous fn>").to_owned();
+
+        Self { name, location }
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(location) = self.location {
-            write!(f, "{location} {}", self.kind)
-        } else {
-            write!(f, "{}", self.kind)
+        write!(f, "error: {}", self.kind)?;
+
+        // Lines after the first are indented to line up with the line numbers
+        // in the snippet.
+        let gutter = " ".repeat(
+            self.location
+                .as_ref()
+                .map_or(1, |location| location.line_no().to_string().len()),
+        );
+
+        if let Some(location) = &self.location {
+            write!(f, "\n{gutter}--> {location}")?;
+
+            if let Some(snippet) = location.snippet() {
+                write!(f, "\n{snippet}")?;
+            }
+        }
+
+        if let Some(hint) = &self.hint {
+            write!(f, "\n{gutter} = help: {hint}")?;
+        }
+
+        if !self.backtrace.is_empty() {
+            write!(
+                f,
+                "\n\nbacktrace (most recent call first, frames replaced by \
+                 tail calls are not shown):"
+            )?;
+        }
+
+        // Deep recursion would print thousands of frames, so only the
+        // outermost and innermost ones are shown.
+        let omitted = self.backtrace.len().saturating_sub(2 * BACKTRACE_EDGE);
+
+        for (index, frame) in self.backtrace.iter().enumerate() {
+            if omitted > 0 && index == BACKTRACE_EDGE {
+                write!(f, "\n  ... {omitted} frames omitted ...")?;
+            }
+
+            if omitted > 0
+                && (BACKTRACE_EDGE..BACKTRACE_EDGE + omitted).contains(&index)
+            {
+                continue;
+            }
+
+            write!(f, "\n  {index:>2}: {} at {}", frame.name, frame.location)?;
         }
+
+        Ok(())
     }
 }
 
@@ -35,25 +161,40 @@ impl std::error::Error for Error {
 
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
-        Self { location: None, kind: error.into() }
+        Self {
+            location: None,
+            kind: error.into(),
+            hint: None,
+            backtrace: Vec::new(),
+        }
     }
 }
 
 impl From<pest::error::Error<Rule>> for Error {
     fn from(error: pest::error::Error<Rule>) -> Self {
-        Self { location: None, kind: Box::new(error).into() }
+        Self {
+            location: None,
+            kind: Box::new(error).into(),
+            hint: None,
+            backtrace: Vec::new(),
+        }
     }
 }
 
 impl From<rustyline::error::ReadlineError> for Error {
     fn from(error: rustyline::error::ReadlineError) -> Self {
-        Self { location: None, kind: error.into() }
+        Self {
+            location: None,
+            kind: error.into(),
+            hint: None,
+            backtrace: Vec::new(),
+        }
     }
 }
 
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Self {
-        Error { location: None, kind }
+        Error { location: None, kind, hint: None, backtrace: Vec::new() }
     }
 }
 
@@ -63,12 +204,15 @@ pub enum ErrorKind {
     #[error("Usage: {} [FILENAME]", env!("CARGO_PKG_NAME"))]
     UsageError,
 
-    #[error(transparent)]
+    #[error("{}", source.variant.message())]
     ParserError {
         #[from]
         source: Box<pest::error::Error<Rule>>,
     },
 
+    #[error("Integer literal {0} doesn't fit in 64 bits.")]
+    IntegerLiteralOutOfRange(String),
+
     #[error(transparent)]
     IOError {
         #[from]
@@ -85,15 +229,61 @@ pub enum ErrorKind {
     #[error("Undefined module '{0}'")]
     ModuleNotDefined(String),
 
+    #[error(
+        "Unable to find module '{name}', searched: {}",
+        searched.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    ModuleNotFound { name: String, searched: Vec<PathBuf> },
+
+    #[error("Unable to find module '{0}' in the standard library")]
+    StdModuleNotFound(String),
+
+    #[error("Invalid import of module '{name}': {reason}")]
+    InvalidImport { name: String, reason: String },
+
+    #[error("Circular import: {}", .0.join(" -> "))]
+    CircularImport(Vec<String>),
+
     #[error("Undefined symbol '{0}'")]
     SymbolNotDefined(String),
 
+    #[error("Symbol '{name}' is private to module '{module}'")]
+    PrivateSymbol { module: String, name: String },
+
     #[error("Already defined symbol '{0}'")]
     SymbolDefined(String),
 
     #[error("Value '{0}' is not callable.")]
     NotCallable(Value),
 
+    #[error("Unable to match '{value}' with pattern '{pattern}'.")]
+    PatternMismatch { pattern: Pattern, value: Value },
+
+    #[error("`recur` can only be used in tail position of `loop` or `fn`.")]
+    RecurNotInTailPosition,
+
+    #[error("Uncaught error '{0}'.")]
+    Thrown(Value),
+
+    #[error("Maximum call depth of {0} exceeded.")]
+    MaxCallDepth(usize),
+
+    #[error(
+        "Macro '{0}' can only be used at the top level after it is defined."
+    )]
+    MacroNotExpanded(Value),
+
+    #[error("`unquote` can only be used inside `quasiquote`.")]
+    UnquoteOutsideQuasiquote,
+
+    #[error(
+        "`unquote-splicing` can only be used inside a list in `quasiquote`."
+    )]
+    UnquoteSplicingOutsideList,
+
+    #[error("Unable to convert '{0}' to code.")]
+    NotCode(Value),
+
     // Parameters
     #[error("Only the last parameter of a function may be a rest parameter.")]
     NonLastParameterIsRest,
@@ -106,15 +296,24 @@ pub enum ErrorKind {
     #[error("'{0}' is not a valid type.")]
     InvalidParameterType(String),
 
-    #[error("Invalid argument '{actual}', expected '{expected:?}'")]
+    #[error(
+        "Invalid argument '{actual}', expected {}.",
+        format_types(.expected)
+    )]
     InvalidArgument { expected: Vec<ParameterType>, actual: Value },
 
+    #[error("Unable to convert '{value}' to {target}.")]
+    InvalidConversion { value: Value, target: &'static str },
+
     #[error("Expect {expected} args, got {actual}.")]
     WrongAmountOfFixedArgs { expected: usize, actual: usize },
 
     #[error("Expect at least {expected} args, got {actual}.")]
     WrongAmountOfMinArgs { expected: usize, actual: usize },
 
+    #[error("Expect at most {expected} args, got {actual}.")]
+    WrongAmountOfMaxArgs { expected: usize, actual: usize },
+
     // Functions
     #[error("Called `tail` on empty list.")]
     TailOnEmptyList,
@@ -125,6 +324,26 @@ pub enum ErrorKind {
     #[error("Unable to parse {0:?} as number")]
     ParseNumberError(String),
 
-    #[error("Unable to parse {0} as integer.")]
-    InvalidInteger(f64),
+    #[error("Integer {0} doesn't fit in 64 bits.")]
+    IntegerOutOfRange(String),
+
+    #[error("Integer overflow when trying to {op} {lhs} and {rhs}.")]
+    IntegerOverflow { op: &'static str, lhs: Value, rhs: Value },
+
+    #[error("Unable to divide {0} by zero.")]
+    DivisionByZero(Value),
+
+    #[error("Expected a non-negative integer, got {0}.")]
+    NegativeInteger(i64),
+
+    #[error("Invalid format string {format:?}: {reason}.")]
+    InvalidFormatString { format: String, reason: &'static str },
+
+    #[error("No argument for placeholder '{{{0}}}'.")]
+    FormatArgumentMissing(String),
+}
+
+/// Formats the types an argument may have, e.g. `string or nil`.
+fn format_types(types: &[ParameterType]) -> String {
+    types.iter().map(ToString::to_string).collect::<Vec<_>>().join(" or ")
 }
@@ -1,7 +1,10 @@
+use std::fmt::Write;
 use std::process::Command;
 
-use crate::parser::ast::{Node, NodeData, Special};
+use crate::location::Location;
+use crate::parser::ast::{ImportFilter, Node, NodeData, Special};
 use crate::parser::parameters::Parameters;
+use crate::parser::pattern::Pattern;
 use crate::visitor::Visitor;
 
 pub(crate) struct GraphVisitor {
@@ -82,7 +85,7 @@ impl GraphVisitor {
             }
         };
 
-        self.body.push_str(&format!("  node{} {label_str}\n", self.counter));
+        let _ = writeln!(self.body, "  node{} {label_str}", self.counter);
 
         self.increment()
     }
@@ -102,7 +105,7 @@ impl GraphVisitor {
         label: Option<&str>,
         directed: bool,
     ) {
-        self.body.push_str(&format!("  node{node1} -> node{node2}"));
+        let _ = write!(self.body, "  node{node1} -> node{node2}");
 
         let mut args: Vec<String> = Vec::new();
 
@@ -115,7 +118,7 @@ impl GraphVisitor {
         }
 
         if !args.is_empty() {
-            self.body.push_str(&format!(" [{}]", args.join(", ")));
+            let _ = write!(self.body, " [{}]", args.join(", "));
         }
 
         self.body.push('\n');
@@ -163,6 +166,23 @@ impl GraphVisitor {
         }
     }
 
+    fn bindings_and_body(
+        &mut self,
+        parent_node: usize,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+    ) {
+        for (pattern, value) in bindings {
+            let binding_node = self.new_node(&format!("binding\n{pattern}"));
+
+            self.connect_nodes(parent_node, binding_node);
+
+            self.accept_and_connect(binding_node, value);
+        }
+
+        self.accept_and_connect_many_with_label(parent_node, body, "body");
+    }
+
     fn accept_and_connect_many_with_label(
         &mut self,
         parent_node: usize,
@@ -173,6 +193,75 @@ impl GraphVisitor {
             self.accept_and_connect_with_label(parent_node, node, label);
         }
     }
+
+    fn visit_special(&mut self, special: &Special, location: Location) {
+        match special {
+            Special::If { condition, then, else_branch } => {
+                self.visit_if(condition, then, else_branch.as_ref());
+            },
+            Special::Fn { parameters, body } => {
+                self.visit_fn(parameters, body);
+            },
+            Special::Defmacro { name, parameters, body } => {
+                self.visit_defmacro(name, parameters, body, location);
+            },
+            Special::Do { body } => self.visit_do(body),
+            Special::Set { name, value } => {
+                self.visit_set(name, value, location);
+            },
+            Special::Var { name, value } => {
+                self.visit_var(name, value, location);
+            },
+            Special::Import { name, prefix, filter, rename } => {
+                self.visit_import(
+                    name,
+                    prefix.as_ref(),
+                    filter.as_ref(),
+                    rename,
+                    location,
+                );
+            },
+            Special::And { values } => self.visit_and(values),
+            Special::Or { values } => self.visit_or(values),
+            Special::NilCoalesce { values } => {
+                self.visit_nil_coalesce(values);
+            },
+            Special::Cond { clauses } => {
+                self.visit_cond(clauses);
+            },
+            Special::Match { value, clauses } => {
+                self.visit_match(value, clauses, location);
+            },
+            Special::Let { bindings, body } => {
+                self.visit_let(bindings, body, location);
+            },
+            Special::Loop { bindings, body } => {
+                self.visit_loop(bindings, body, location);
+            },
+            Special::Recur { arguments } => {
+                self.visit_recur(arguments, location);
+            },
+            Special::While { condition, body } => {
+                self.visit_while(condition, body);
+            },
+            Special::Dotimes { name, count, body } => {
+                self.visit_dotimes(name, count, body, location);
+            },
+            Special::Try { body, catch, finally } => {
+                self.visit_try(body, catch.as_ref(), finally.as_ref());
+            },
+            Special::Quote { datum } => self.visit_quote(datum),
+            Special::Quasiquote { datum } => {
+                self.visit_quasiquote(datum);
+            },
+            Special::Unquote { value } => {
+                self.visit_unquote(value, location);
+            },
+            Special::UnquoteSplicing { value } => {
+                self.visit_unquote_splicing(value, location);
+            },
+        }
+    }
 }
 
 impl Visitor<()> for GraphVisitor {
@@ -180,34 +269,23 @@ impl Visitor<()> for GraphVisitor {
         match node.data() {
             NodeData::Main(nodes) => self.visit_main(nodes),
             NodeData::Special(special) => {
-                match &**special {
-                    Special::If { condition, then, else_branch } => {
-                        self.visit_if(condition, then, else_branch.as_ref())
-                    },
-                    Special::Fn { parameters, body } => {
-                        self.visit_fn(parameters, body);
-                    },
-                    Special::Set { name, value } => {
-                        self.visit_set(name, value, node.location());
-                    },
-                    Special::Var { name, value } => {
-                        self.visit_var(name, value, node.location());
-                    },
-                    Special::Import { name, prefix } => {
-                        self.visit_import(name, prefix.as_ref());
-                    },
-                }
+                self.visit_special(special, node.location());
             },
             NodeData::List { literal, nodes } => {
                 self.visit_list(*literal, nodes);
             },
+            NodeData::Map(entries) => self.visit_map(entries),
+            NodeData::FString(pieces) => self.visit_fstring(pieces),
             NodeData::Symbol { module, value } => {
                 self.visit_symbol(module.as_ref(), value, node.location());
             },
             NodeData::Keyword(keyword) => {
                 self.new_node(&format!("keyword:\n{keyword}"));
             },
-            NodeData::Number(number) => {
+            NodeData::Integer(integer) => {
+                self.new_node(&format!("integer:\n{integer}"));
+            },
+            NodeData::Float(number) => {
                 self.new_node(&format!("number:\n{number}"));
             },
             NodeData::String(string) => {
@@ -237,6 +315,25 @@ impl Visitor<()> for GraphVisitor {
         self.accept_and_connect_many_with_label(parent_node, body, "body");
     }
 
+    fn visit_defmacro(
+        &mut self,
+        name: &str,
+        parameters: &Parameters,
+        body: &[Node],
+        _location: Location,
+    ) {
+        let parent_node =
+            self.new_node(&format!("defmacro '{name}'\n[{parameters}]"));
+
+        self.accept_and_connect_many_with_label(parent_node, body, "body");
+    }
+
+    fn visit_do(&mut self, body: &[Node]) {
+        let parent_node = self.new_node("do");
+
+        self.accept_and_connect_many(parent_node, body);
+    }
+
     fn visit_if(
         &mut self,
         condition: &Node,
@@ -258,46 +355,229 @@ impl Visitor<()> for GraphVisitor {
         }
     }
 
-    fn visit_import(&mut self, name: &str, prefix: Option<&String>) {
-        self.new_node(&format!(
+    fn visit_and(&mut self, values: &[Node]) {
+        let parent_node = self.new_node("and");
+
+        self.accept_and_connect_many(parent_node, values);
+    }
+
+    fn visit_or(&mut self, values: &[Node]) {
+        let parent_node = self.new_node("or");
+
+        self.accept_and_connect_many(parent_node, values);
+    }
+
+    fn visit_nil_coalesce(&mut self, values: &[Node]) {
+        let parent_node = self.new_node("??");
+
+        self.accept_and_connect_many(parent_node, values);
+    }
+
+    fn visit_cond(&mut self, clauses: &[(Node, Node)]) {
+        let parent_node = self.new_node("cond");
+
+        for (test, result) in clauses {
+            let clause_node = self.new_node("clause");
+
+            self.connect_nodes(parent_node, clause_node);
+
+            self.accept_and_connect_with_label(clause_node, test, "test");
+            self.accept_and_connect_with_label(clause_node, result, "result");
+        }
+    }
+
+    fn visit_match(
+        &mut self,
+        value: &Node,
+        clauses: &[(Pattern, Node)],
+        _location: Location,
+    ) {
+        let parent_node = self.new_node("match");
+
+        self.accept_and_connect_with_label(parent_node, value, "value");
+
+        for (pattern, result) in clauses {
+            let clause_node = self.new_node(&format!("pattern\n{pattern}"));
+
+            self.connect_nodes(parent_node, clause_node);
+
+            self.accept_and_connect_with_label(clause_node, result, "result");
+        }
+    }
+
+    fn visit_import(
+        &mut self,
+        name: &str,
+        prefix: Option<&String>,
+        filter: Option<&ImportFilter>,
+        rename: &[(String, String)],
+        _location: Location,
+    ) {
+        let mut label = format!(
             "import '{name}'\nas '{}'",
             if let Some(prefix) = prefix { prefix } else { name }
-        ));
+        );
+
+        match filter {
+            Some(ImportFilter::Only(names)) => {
+                let _ = write!(label, "\nonly {}", names.join(" "));
+            },
+            Some(ImportFilter::Exclude(names)) => {
+                let _ = write!(label, "\nexclude {}", names.join(" "));
+            },
+            None => (),
+        }
+
+        for (from, to) in rename {
+            let _ = write!(label, "\nrename {from} to {to}");
+        }
+
+        self.new_node(&label);
     }
 
-    fn visit_set(
+    fn visit_let(
         &mut self,
-        name: &str,
-        value: &Node,
-        _location: crate::location::Location,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        _location: Location,
     ) {
-        let parent_node = self.new_node(&format!("set\n'{name}'"));
+        let parent_node = self.new_node("let");
 
-        self.accept_and_connect(parent_node, value);
+        self.bindings_and_body(parent_node, bindings, body);
+    }
+
+    fn visit_loop(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        _location: Location,
+    ) {
+        let parent_node = self.new_node("loop");
+
+        self.bindings_and_body(parent_node, bindings, body);
+    }
+
+    fn visit_recur(&mut self, arguments: &[Node], _location: Location) {
+        let parent_node = self.new_node("recur");
+
+        self.accept_and_connect_many(parent_node, arguments);
     }
 
-    fn visit_var(
+    fn visit_while(&mut self, condition: &Node, body: &[Node]) {
+        let parent_node = self.new_node("while");
+
+        self.accept_and_connect_with_label(parent_node, condition, "condition");
+
+        self.accept_and_connect_many_with_label(parent_node, body, "body");
+    }
+
+    fn visit_dotimes(
         &mut self,
         name: &str,
-        value: &Node,
-        _location: crate::location::Location,
+        count: &Node,
+        body: &[Node],
+        _location: Location,
+    ) {
+        let parent_node = self.new_node(&format!("dotimes\n'{name}'"));
+
+        self.accept_and_connect_with_label(parent_node, count, "count");
+
+        self.accept_and_connect_many_with_label(parent_node, body, "body");
+    }
+
+    fn visit_try(
+        &mut self,
+        body: &[Node],
+        catch: Option<&(String, Vec<Node>)>,
+        finally: Option<&Vec<Node>>,
     ) {
+        let parent_node = self.new_node("try");
+
+        self.accept_and_connect_many_with_label(parent_node, body, "body");
+
+        if let Some((name, body)) = catch {
+            let catch_node = self.new_node(&format!("catch\n'{name}'"));
+
+            self.connect_nodes(parent_node, catch_node);
+
+            self.accept_and_connect_many_with_label(catch_node, body, "body");
+        }
+
+        if let Some(body) = finally {
+            let finally_node = self.new_node("finally");
+
+            self.connect_nodes(parent_node, finally_node);
+
+            self.accept_and_connect_many_with_label(finally_node, body, "body");
+        }
+    }
+
+    fn visit_set(&mut self, name: &str, value: &Node, _location: Location) {
+        let parent_node = self.new_node(&format!("set\n'{name}'"));
+
+        self.accept_and_connect(parent_node, value);
+    }
+
+    fn visit_var(&mut self, name: &str, value: &Node, _location: Location) {
         let parent_node = self.new_node(&format!("var\n'{name}'"));
 
         self.accept_and_connect(parent_node, value);
     }
 
+    fn visit_quote(&mut self, datum: &Node) {
+        let parent_node = self.new_node("quote");
+
+        self.accept_and_connect(parent_node, datum);
+    }
+
+    fn visit_quasiquote(&mut self, datum: &Node) {
+        let parent_node = self.new_node("quasiquote");
+
+        self.accept_and_connect(parent_node, datum);
+    }
+
+    fn visit_unquote(&mut self, value: &Node, _location: Location) {
+        let parent_node = self.new_node("unquote");
+
+        self.accept_and_connect(parent_node, value);
+    }
+
+    fn visit_unquote_splicing(&mut self, value: &Node, _location: Location) {
+        let parent_node = self.new_node("unquote-splicing");
+
+        self.accept_and_connect(parent_node, value);
+    }
+
     fn visit_list(&mut self, literal: bool, nodes: &[Node]) {
         let parent_node = self.new_node(if literal { "list" } else { "form" });
 
         self.accept_and_connect_many(parent_node, nodes);
     }
 
+    fn visit_map(&mut self, entries: &[(Node, Node)]) {
+        let parent_node = self.new_node("map");
+
+        for (key, value) in entries {
+            let entry_node = self.new_node("entry");
+
+            self.connect_nodes(parent_node, entry_node);
+
+            self.accept_and_connect_with_label(entry_node, key, "key");
+            self.accept_and_connect_with_label(entry_node, value, "value");
+        }
+    }
+
+    fn visit_fstring(&mut self, pieces: &[Node]) {
+        let parent_node = self.new_node("fstring");
+
+        self.accept_and_connect_many(parent_node, pieces);
+    }
+
     fn visit_symbol(
         &mut self,
         module: Option<&String>,
         value: &str,
-        _location: crate::location::Location,
+        _location: Location,
     ) {
         let symbol = if let Some(module) = module {
             format!("{module}/{value}")
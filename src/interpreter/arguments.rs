@@ -1,5 +1,5 @@
 use super::environment::Environment;
-use super::value::Callable;
+use super::value::{Callable, Map};
 use super::{Parameters, Value};
 use crate::error::{Error, ErrorKind};
 use crate::parser::parameters::Parameter;
@@ -31,10 +31,20 @@ impl<'a> Arguments<'a> {
         self.arguments.len()
     }
 
-    pub fn add_to_env(self, env: &mut Environment) -> Result<()> {
-        for (parameter, argument) in self.parameters.iter().zip(self.arguments)
-        {
-            env.insert(parameter.name().to_owned(), argument)?;
+    /// Binds the arguments to their parameters in `env`. A rest parameter is
+    /// bound to a list of the remaining arguments, missing optional
+    /// parameters are bound to `nil`.
+    pub fn add_to_env(self, env: &Environment) -> Result<()> {
+        let mut arguments = self.arguments.into_iter();
+
+        for parameter in self.parameters {
+            let value = if parameter.is_rest_param() {
+                Value::List(arguments.by_ref().collect())
+            } else {
+                arguments.next().unwrap_or(Value::Nil)
+            };
+
+            env.insert(parameter.name().to_owned(), value)?;
         }
 
         Ok(())
@@ -91,6 +101,16 @@ impl<'a> Arguments<'a> {
         list.clone()
     }
 
+    pub fn unwrap_map(&self, index: usize) -> Map {
+        let argument = &self.arguments[index];
+
+        let Value::Map(map) = argument else {
+            panic!("Called unwrap_map on non-Value::Map")
+        };
+
+        map.clone()
+    }
+
     pub fn unwrap_module(&self, index: usize) -> &Environment {
         let argument = &self.arguments[index];
 
@@ -121,43 +141,48 @@ impl<'a> Arguments<'a> {
         keyword.clone()
     }
 
-    pub fn unwrap_number(&self, index: usize) -> f64 {
+    pub fn unwrap_integer(&self, index: usize) -> i64 {
         let argument = &self.arguments[index];
 
-        let Value::Number(number) = argument else {
-            panic!("Called unwrap_number on non-Value::Number")
+        let Value::Integer(integer) = argument else {
+            panic!("Called unwrap_integer on non-Value::Integer")
         };
 
-        *number
+        *integer
     }
 
-    pub fn unwrap_numbers(&self) -> Vec<f64> {
-        self.arguments
-            .iter()
-            .map(|v| {
-                let Value::Number(number) = v else {
-                    panic!("Called unwrap_numbers on non-Value::Number")
-                };
-                *number
-            })
-            .collect()
+    /// Returns an integer or float argument as a float.
+    pub fn unwrap_number(&self, index: usize) -> f64 {
+        self.arguments[index].as_float().expect(
+            "Called unwrap_number on non-Value::Integer or non-Value::Float",
+        )
     }
 
     fn check_length(&self) -> Result<()> {
-        if self.parameters.has_rest_param() {
-            if self.parameters.len() > self.arguments.len() {
-                Err(ErrorKind::WrongAmountOfMinArgs {
-                    expected: self.parameters.len(),
-                    actual: self.arguments.len(),
-                }
-                .into())
-            } else {
-                Ok(())
-            }
-        } else if self.parameters.len() != self.arguments.len() {
+        let required = self
+            .parameters
+            .iter()
+            .filter(|p| !p.is_optional() && !p.is_rest_param())
+            .count();
+
+        let has_rest_param = self.parameters.has_rest_param();
+        let is_fixed = !has_rest_param && required == self.parameters.len();
+
+        let actual = self.arguments.len();
+
+        if is_fixed && actual != required {
             Err(ErrorKind::WrongAmountOfFixedArgs {
+                expected: required,
+                actual,
+            }
+            .into())
+        } else if actual < required {
+            Err(ErrorKind::WrongAmountOfMinArgs { expected: required, actual }
+                .into())
+        } else if !has_rest_param && actual > self.parameters.len() {
+            Err(ErrorKind::WrongAmountOfMaxArgs {
                 expected: self.parameters.len(),
-                actual: self.arguments.len(),
+                actual,
             }
             .into())
         } else {
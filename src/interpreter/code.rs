@@ -0,0 +1,836 @@
+use super::Value;
+use crate::error::{Error, ErrorKind};
+use crate::location::Location;
+use crate::parser::ast::{ImportFilter, Node, NodeData, Special};
+use crate::parser::parameters::Parameters;
+use crate::parser::pattern::Pattern;
+use crate::Result;
+
+// Bracket lists, f-strings and reader syntax in code are represented as lists
+// starting with these symbols. Those of bracket lists and f-strings start with
+// `#`, which can't be written in a symbol, so they don't hide functions called
+// `list` or `fstring`.
+pub(super) const LIST: &str = "#list";
+pub(super) const FSTRING: &str = "#fstring";
+pub(super) const QUOTE: &str = "quote";
+pub(super) const QUASIQUOTE: &str = "quasiquote";
+pub(super) const UNQUOTE: &str = "unquote";
+pub(super) const UNQUOTE_SPLICING: &str = "unquote-splicing";
+
+/// Converts quoted data to a value, without evaluating it. Bracket lists are
+/// read as plain lists, e.g. `'[a b]` is `(a b)`.
+pub(crate) fn datum_to_value(node: &Node) -> Value {
+    match node.data() {
+        NodeData::List { nodes, .. } => {
+            Value::List(nodes.iter().map(datum_to_value).collect())
+        },
+        NodeData::Map(entries) => {
+            Value::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        (datum_to_value(key), datum_to_value(value))
+                    })
+                    .collect(),
+            )
+        },
+        _ => code_to_value(node),
+    }
+}
+
+/// Converts code to the value a macro receives it as. Special forms are
+/// lists starting with their name, e.g. `(let (a 1) a)`. Bracket lists are
+/// `(#list ...)` and f-strings `(#fstring ...)`, so they can be told apart
+/// from calls.
+pub(crate) fn code_to_value(node: &Node) -> Value {
+    match node.data() {
+        NodeData::Special(special) => special_to_value(special),
+        NodeData::List { literal, nodes } => {
+            let values = nodes.iter().map(code_to_value).collect();
+
+            if *literal {
+                form(LIST, values)
+            } else {
+                Value::List(values)
+            }
+        },
+        NodeData::Map(entries) => {
+            Value::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        (code_to_value(key), code_to_value(value))
+                    })
+                    .collect(),
+            )
+        },
+        NodeData::FString(pieces) => {
+            form(FSTRING, pieces.iter().map(code_to_value).collect())
+        },
+        NodeData::Symbol { module, value } => {
+            Value::Symbol(if let Some(module) = module {
+                format!("{module}/{value}")
+            } else {
+                value.clone()
+            })
+        },
+        NodeData::Keyword(keyword) => Value::Keyword(keyword.clone()),
+        NodeData::Integer(integer) => Value::Integer(*integer),
+        NodeData::Float(float) => Value::Float(*float),
+        NodeData::String(string) => Value::String(string.clone()),
+        NodeData::True => Value::Boolean(true),
+        NodeData::False => Value::Boolean(false),
+        NodeData::Nil => Value::Nil,
+        NodeData::Main(_) => unreachable!("Encountered Main inside code."),
+    }
+}
+
+/// Converts a value to code, e.g. the expansion of a macro. This is the
+/// inverse of `code_to_value`. All nodes are placed at `location`.
+pub(crate) fn value_to_node(
+    value: &Value,
+    location: &Location,
+) -> Result<Node> {
+    let data = match value {
+        Value::Nil => NodeData::Nil,
+        Value::Boolean(true) => NodeData::True,
+        Value::Boolean(false) => NodeData::False,
+        Value::Integer(integer) => NodeData::Integer(*integer),
+        Value::Float(float) => NodeData::Float(*float),
+        Value::String(string) => NodeData::String(string.clone()),
+        Value::Keyword(keyword) => NodeData::Keyword(keyword.clone()),
+        Value::Symbol(symbol) => symbol_data(symbol),
+        Value::Map(map) => {
+            NodeData::Map(
+                map.iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            value_to_node(key, location)?,
+                            value_to_node(value, location)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+            )
+        },
+        Value::List(values) => {
+            return FormReader { form: value, location, usage: "" }.list(values)
+        },
+        Value::Callable(_) | Value::Module(_) => {
+            return Err(ErrorKind::NotCode(value.clone()).into())
+        },
+    };
+
+    Ok(Node::new(data, location.clone()))
+}
+
+/// Returns a list starting with the symbol `name`.
+pub(super) fn form(name: &str, values: Vec<Value>) -> Value {
+    Value::List(
+        std::iter::once(Value::Symbol(name.to_owned())).chain(values).collect(),
+    )
+}
+
+/// Returns the items of a list written in brackets in source, like the
+/// bindings of `let`. These are `(#list ...)` if they were passed to a macro
+/// as code.
+fn bracket_items(value: &Value) -> Option<&[Value]> {
+    match value {
+        Value::List(values) => {
+            match values.split_first() {
+                Some((Value::Symbol(head), items)) if head == LIST => {
+                    Some(items)
+                },
+                _ => Some(values),
+            }
+        },
+        _ => None,
+    }
+}
+
+fn symbol(name: &str) -> Value {
+    Value::Symbol(name.to_owned())
+}
+
+fn symbol_data(symbol: &str) -> NodeData {
+    match symbol.split_once('/') {
+        Some((module, value)) if !module.is_empty() && !value.is_empty() => {
+            NodeData::Symbol {
+                module: Some(module.to_owned()),
+                value: value.to_owned(),
+            }
+        },
+        _ => NodeData::Symbol { module: None, value: symbol.to_owned() },
+    }
+}
+
+fn special_to_value(special: &Special) -> Value {
+    match special {
+        Special::Fn { parameters, body } => {
+            body_form("fn", vec![parameters_to_value(parameters)], body)
+        },
+        Special::Defmacro { name, parameters, body } => {
+            body_form(
+                "defmacro",
+                vec![symbol(name), parameters_to_value(parameters)],
+                body,
+            )
+        },
+        Special::Do { body } => body_form("do", Vec::new(), body),
+        Special::If { condition, then, else_branch } => {
+            form(
+                "if",
+                [condition, then]
+                    .into_iter()
+                    .chain(else_branch)
+                    .map(code_to_value)
+                    .collect(),
+            )
+        },
+        Special::Import { name, prefix, filter, rename } => {
+            import_to_value(name, prefix.as_ref(), filter.as_ref(), rename)
+        },
+        Special::And { values } => body_form("and", Vec::new(), values),
+        Special::Or { values } => body_form("or", Vec::new(), values),
+        Special::NilCoalesce { values } => body_form("??", Vec::new(), values),
+        Special::Cond { clauses } => {
+            form(
+                "cond",
+                clauses
+                    .iter()
+                    .flat_map(|(test, result)| {
+                        [code_to_value(test), code_to_value(result)]
+                    })
+                    .collect(),
+            )
+        },
+        Special::Match { value, clauses } => {
+            form(
+                "match",
+                std::iter::once(code_to_value(value))
+                    .chain(clauses.iter().flat_map(|(pattern, result)| {
+                        [pattern_to_value(pattern), code_to_value(result)]
+                    }))
+                    .collect(),
+            )
+        },
+        Special::Let { bindings, body } => {
+            body_form("let", vec![bindings_to_value(bindings)], body)
+        },
+        Special::Loop { bindings, body } => {
+            body_form("loop", vec![bindings_to_value(bindings)], body)
+        },
+        Special::Recur { arguments } => {
+            body_form("recur", Vec::new(), arguments)
+        },
+        Special::While { condition, body } => {
+            body_form("while", vec![code_to_value(condition)], body)
+        },
+        Special::Dotimes { name, count, body } => {
+            let binding = Value::List(vec![symbol(name), code_to_value(count)]);
+
+            body_form("dotimes", vec![binding], body)
+        },
+        Special::Try { body, catch, finally } => {
+            let mut values: Vec<Value> =
+                body.iter().map(code_to_value).collect();
+
+            if let Some((name, body)) = catch {
+                values.push(body_form("catch", vec![symbol(name)], body));
+            }
+
+            if let Some(body) = finally {
+                values.push(body_form("finally", Vec::new(), body));
+            }
+
+            form("try", values)
+        },
+        Special::Set { name, value } => {
+            form("set", vec![symbol(name), code_to_value(value)])
+        },
+        Special::Var { name, value } => {
+            form("var", vec![symbol(name), code_to_value(value)])
+        },
+        Special::Quote { datum } => form(QUOTE, vec![datum_to_value(datum)]),
+        Special::Quasiquote { datum } => {
+            form(QUASIQUOTE, vec![datum_to_value(datum)])
+        },
+        Special::Unquote { value } => form(UNQUOTE, vec![code_to_value(value)]),
+        Special::UnquoteSplicing { value } => {
+            form(UNQUOTE_SPLICING, vec![code_to_value(value)])
+        },
+    }
+}
+
+/// Returns a list starting with the symbol `name`, followed by `values` and
+/// then `body`.
+fn body_form(name: &str, mut values: Vec<Value>, body: &[Node]) -> Value {
+    values.extend(body.iter().map(code_to_value));
+
+    form(name, values)
+}
+
+fn import_to_value(
+    name: &str,
+    prefix: Option<&String>,
+    filter: Option<&ImportFilter>,
+    rename: &[(String, String)],
+) -> Value {
+    let names = |names: &[String]| {
+        Value::List(names.iter().map(|n| symbol(n)).collect())
+    };
+
+    let mut values = vec![symbol(name)];
+
+    if let Some(prefix) = prefix {
+        values.push(Value::Keyword("as".to_owned()));
+        values.push(Value::String(prefix.clone()));
+    }
+
+    match filter {
+        Some(ImportFilter::Only(only)) => {
+            values.push(Value::Keyword("only".to_owned()));
+            values.push(names(only));
+        },
+        Some(ImportFilter::Exclude(exclude)) => {
+            values.push(Value::Keyword("exclude".to_owned()));
+            values.push(names(exclude));
+        },
+        None => (),
+    }
+
+    if !rename.is_empty() {
+        values.push(Value::Keyword("rename".to_owned()));
+        values.push(Value::Map(
+            rename
+                .iter()
+                .map(|(from, to)| (symbol(from), symbol(to)))
+                .collect(),
+        ));
+    }
+
+    form("import", values)
+}
+
+fn parameters_to_value(parameters: &Parameters) -> Value {
+    Value::List(parameters.to_tokens().into_iter().map(Value::Symbol).collect())
+}
+
+fn bindings_to_value(bindings: &[(Pattern, Node)]) -> Value {
+    Value::List(
+        bindings
+            .iter()
+            .flat_map(|(pattern, value)| {
+                [pattern_to_value(pattern), code_to_value(value)]
+            })
+            .collect(),
+    )
+}
+
+fn pattern_to_value(pattern: &Pattern) -> Value {
+    match pattern {
+        Pattern::Symbol(name) => symbol(name),
+        Pattern::Literal(value) => value.clone(),
+        Pattern::List { items, rest } => {
+            let mut values: Vec<Value> =
+                items.iter().map(pattern_to_value).collect();
+
+            if let Some(rest) = rest {
+                values.push(symbol("&"));
+                values.push(symbol(rest));
+            }
+
+            Value::List(values)
+        },
+    }
+}
+
+/// Returns how the special form `name` is written, or `None` if `name` isn't
+/// a special form.
+fn usage(name: &str) -> Option<&'static str> {
+    let usage = match name {
+        "and" => "(and values...)",
+        "cond" => "(cond test result ...)",
+        "defmacro" => "(defmacro name [parameters] body...)",
+        "defn" => "(defn name [parameters] body...)",
+        "do" => "(do body...)",
+        "dotimes" => "(dotimes [name count] body...)",
+        "fn" => "(fn [parameters] body...)",
+        "if" => "(if condition then else)",
+        "import" => {
+            "(import module :as \"prefix\" :only [names] :exclude [names] \
+             :rename {from to})"
+        },
+        "let" => "(let [pattern value ...] body...)",
+        "loop" => "(loop [pattern value ...] body...)",
+        "match" => "(match value pattern result ...)",
+        "??" => "(?? values...)",
+        "or" => "(or values...)",
+        "recur" => "(recur arguments...)",
+        "set" => "(set name value)",
+        "try" => "(try body... (catch name body...) (finally body...))",
+        "var" => "(var name value)",
+        "while" => "(while condition body...)",
+        QUOTE => "(quote datum)",
+        QUASIQUOTE => "(quasiquote datum)",
+        UNQUOTE => "(unquote value)",
+        UNQUOTE_SPLICING => "(unquote-splicing value)",
+        _ => return None,
+    };
+
+    Some(usage)
+}
+
+/// Reads a list as code, recognizing special forms by their name.
+struct FormReader<'a> {
+    form: &'a Value,
+    location: &'a Location,
+    usage: &'static str,
+}
+
+impl FormReader<'_> {
+    fn list(mut self, values: &[Value]) -> Result<Node> {
+        let data = match values.split_first() {
+            Some((Value::Symbol(head), arguments)) if head == LIST => {
+                NodeData::List { literal: true, nodes: self.nodes(arguments)? }
+            },
+            Some((Value::Symbol(head), arguments)) if head == FSTRING => {
+                NodeData::FString(self.nodes(arguments)?)
+            },
+            Some((Value::Symbol(head), arguments)) => {
+                if let Some(usage) = usage(head) {
+                    self.usage = usage;
+
+                    NodeData::Special(Box::new(self.special(head, arguments)?))
+                } else {
+                    NodeData::List {
+                        literal: false,
+                        nodes: self.nodes(values)?,
+                    }
+                }
+            },
+            _ => NodeData::List { literal: false, nodes: self.nodes(values)? },
+        };
+
+        Ok(Node::new(data, self.location.clone()))
+    }
+
+    fn special(&self, name: &str, arguments: &[Value]) -> Result<Special> {
+        let special = match (name, arguments) {
+            ("fn" | "defn" | "defmacro", _) => {
+                self.definition(name, arguments)?
+            },
+            ("do", body) => Special::Do { body: self.nodes(body)? },
+            ("if", [condition, then]) => {
+                Special::If {
+                    condition: self.node(condition)?,
+                    then: self.node(then)?,
+                    else_branch: None,
+                }
+            },
+            ("if", [condition, then, else_branch]) => {
+                Special::If {
+                    condition: self.node(condition)?,
+                    then: self.node(then)?,
+                    else_branch: Some(self.node(else_branch)?),
+                }
+            },
+            ("import", [name, options @ ..]) => self.import(name, options)?,
+            ("and", values) => Special::And { values: self.nodes(values)? },
+            ("or", values) => Special::Or { values: self.nodes(values)? },
+            ("??", values) => {
+                Special::NilCoalesce { values: self.nodes(values)? }
+            },
+            ("cond", clauses) => {
+                Special::Cond {
+                    clauses: self.pairs(clauses, |test| self.node(test))?,
+                }
+            },
+            ("match", [value, clauses @ ..]) => {
+                Special::Match {
+                    value: self.node(value)?,
+                    clauses: self
+                        .pairs(clauses, |pattern| self.pattern(pattern))?,
+                }
+            },
+            ("let", [bindings, body @ ..]) => {
+                Special::Let {
+                    bindings: self.bindings(bindings)?,
+                    body: self.body(body)?,
+                }
+            },
+            ("loop", [bindings, body @ ..]) => {
+                Special::Loop {
+                    bindings: self.bindings(bindings)?,
+                    body: self.body(body)?,
+                }
+            },
+            ("recur", arguments) => {
+                Special::Recur { arguments: self.nodes(arguments)? }
+            },
+            ("while", [condition, body @ ..]) => {
+                Special::While {
+                    condition: self.node(condition)?,
+                    body: self.body(body)?,
+                }
+            },
+            ("dotimes", [binding, body @ ..]) => {
+                let Some([name, count]) = bracket_items(binding) else {
+                    return Err(self.invalid());
+                };
+
+                Special::Dotimes {
+                    name: self.name(name)?,
+                    count: self.node(count)?,
+                    body: self.body(body)?,
+                }
+            },
+            ("try", values) => self.try_(values)?,
+            ("set", [name, value]) => {
+                Special::Set {
+                    name: self.name(name)?,
+                    value: self.node(value)?,
+                }
+            },
+            ("var", [name, value]) => {
+                Special::Var {
+                    name: self.name(name)?,
+                    value: self.node(value)?,
+                }
+            },
+            (QUOTE, [datum]) => Special::Quote { datum: self.datum(datum)? },
+            (QUASIQUOTE, [datum]) => {
+                Special::Quasiquote { datum: self.datum(datum)? }
+            },
+            (UNQUOTE, [value]) => Special::Unquote { value: self.node(value)? },
+            (UNQUOTE_SPLICING, [value]) => {
+                Special::UnquoteSplicing { value: self.node(value)? }
+            },
+            _ => return Err(self.invalid()),
+        };
+
+        Ok(special)
+    }
+
+    /// Reads `fn`, `defn` and `defmacro`.
+    fn definition(&self, name: &str, arguments: &[Value]) -> Result<Special> {
+        let special = match (name, arguments) {
+            ("fn", [parameters, body @ ..]) => {
+                self.function(parameters, body)?
+            },
+            ("defn", [name, parameters, body @ ..]) => {
+                let function = self.function(parameters, body)?;
+
+                Special::Var {
+                    name: self.name(name)?,
+                    value: Node::new(
+                        NodeData::Special(Box::new(function)),
+                        self.location.clone(),
+                    ),
+                }
+            },
+            ("defmacro", [name, parameters, body @ ..]) => {
+                let Special::Fn { parameters, body } =
+                    self.function(parameters, body)?
+                else {
+                    unreachable!("FormReader::function should return Fn.")
+                };
+
+                Special::Defmacro { name: self.name(name)?, parameters, body }
+            },
+            _ => return Err(self.invalid()),
+        };
+
+        Ok(special)
+    }
+
+    /// Returns an error for a special form that isn't written as `usage`.
+    fn invalid(&self) -> Error {
+        Error::from(ErrorKind::NotCode(self.form.clone()))
+            .with_hint(Some(format!("expected {}", self.usage)))
+    }
+
+    fn node(&self, value: &Value) -> Result<Node> {
+        value_to_node(value, self.location)
+    }
+
+    fn nodes(&self, values: &[Value]) -> Result<Vec<Node>> {
+        values.iter().map(|value| self.node(value)).collect()
+    }
+
+    /// Reads the body of a form, which may not be empty.
+    fn body(&self, values: &[Value]) -> Result<Vec<Node>> {
+        if values.is_empty() {
+            return Err(self.invalid());
+        }
+
+        self.nodes(values)
+    }
+
+    fn name(&self, value: &Value) -> Result<String> {
+        match value {
+            Value::Symbol(name) if !name.contains('/') => Ok(name.clone()),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    /// Reads quoted data, in which lists are never special forms, except for
+    /// the reader syntax.
+    fn datum(&self, value: &Value) -> Result<Node> {
+        let data = match value {
+            Value::List(values) => {
+                match values.split_first() {
+                    Some((Value::Symbol(head), [value]))
+                        if matches!(
+                            head.as_str(),
+                            QUOTE | QUASIQUOTE | UNQUOTE | UNQUOTE_SPLICING
+                        ) =>
+                    {
+                        let special = match head.as_str() {
+                            QUOTE => {
+                                Special::Quote { datum: self.datum(value)? }
+                            },
+                            QUASIQUOTE => {
+                                Special::Quasiquote {
+                                    datum: self.datum(value)?,
+                                }
+                            },
+                            UNQUOTE => {
+                                Special::Unquote { value: self.node(value)? }
+                            },
+                            _ => {
+                                Special::UnquoteSplicing {
+                                    value: self.node(value)?,
+                                }
+                            },
+                        };
+
+                        NodeData::Special(Box::new(special))
+                    },
+                    Some((Value::Symbol(head), items)) if head == LIST => {
+                        NodeData::List {
+                            literal: true,
+                            nodes: items
+                                .iter()
+                                .map(|value| self.datum(value))
+                                .collect::<Result<_>>()?,
+                        }
+                    },
+                    _ => {
+                        NodeData::List {
+                            literal: false,
+                            nodes: values
+                                .iter()
+                                .map(|value| self.datum(value))
+                                .collect::<Result<_>>()?,
+                        }
+                    },
+                }
+            },
+            Value::Map(map) => {
+                NodeData::Map(
+                    map.iter()
+                        .map(|(key, value)| {
+                            Ok((self.datum(key)?, self.datum(value)?))
+                        })
+                        .collect::<Result<_>>()?,
+                )
+            },
+            value => return self.node(value),
+        };
+
+        Ok(Node::new(data, self.location.clone()))
+    }
+
+    fn function(&self, parameters: &Value, body: &[Value]) -> Result<Special> {
+        Ok(Special::Fn {
+            parameters: self.parameters(parameters)?,
+            body: self.body(body)?,
+        })
+    }
+
+    /// Reads parameters, written as in source. A keyword after a name is its
+    /// type, since `[x:number]` is read as `(x :number)` in quoted data.
+    fn parameters(&self, value: &Value) -> Result<Parameters> {
+        let Some(values) = bracket_items(value) else {
+            return Err(self.invalid());
+        };
+
+        let mut tokens: Vec<String> = Vec::new();
+
+        for value in values {
+            match (value, tokens.last_mut()) {
+                (Value::Symbol(name), _) if !name.contains('/') => {
+                    tokens.push(name.clone());
+                },
+                (Value::Keyword(ptype), Some(token)) => {
+                    token.push(':');
+                    token.push_str(ptype);
+                },
+                _ => return Err(self.invalid()),
+            }
+        }
+
+        let parameters = Parameters::try_from(tokens.join(" ").as_str())
+            .map_err(|_| self.invalid())?;
+
+        // Parsing stops at the first token that isn't a parameter.
+        if parameters.to_tokens().len() == tokens.len() {
+            Ok(parameters)
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    fn pattern(&self, value: &Value) -> Result<Pattern> {
+        let pattern = match value {
+            Value::Symbol(name) if !name.contains('/') && name != "&" => {
+                Pattern::Symbol(name.clone())
+            },
+            Value::Nil
+            | Value::Boolean(_)
+            | Value::Integer(_)
+            | Value::Float(_)
+            | Value::String(_)
+            | Value::Keyword(_) => Pattern::Literal(value.clone()),
+            Value::List(_) => {
+                let values = bracket_items(value).unwrap_or_default();
+
+                let (items, rest) = match values {
+                    [items @ .., Value::Symbol(ampersand), rest]
+                        if ampersand == "&" =>
+                    {
+                        (items, Some(self.name(rest)?))
+                    },
+                    items => (items, None),
+                };
+
+                Pattern::List {
+                    items: items
+                        .iter()
+                        .map(|item| self.pattern(item))
+                        .collect::<Result<_>>()?,
+                    rest,
+                }
+            },
+            _ => return Err(self.invalid()),
+        };
+
+        Ok(pattern)
+    }
+
+    fn bindings(&self, value: &Value) -> Result<Vec<(Pattern, Node)>> {
+        let Some(values) = bracket_items(value) else {
+            return Err(self.invalid());
+        };
+
+        self.pairs(values, |pattern| self.pattern(pattern))
+    }
+
+    /// Reads alternating keys and code, e.g. the clauses of `cond`.
+    fn pairs<T>(
+        &self,
+        values: &[Value],
+        key: impl Fn(&Value) -> Result<T>,
+    ) -> Result<Vec<(T, Node)>> {
+        if values.len() % 2 != 0 {
+            return Err(self.invalid());
+        }
+
+        values
+            .chunks_exact(2)
+            .map(|pair| Ok((key(&pair[0])?, self.node(&pair[1])?)))
+            .collect()
+    }
+
+    fn import(&self, name: &Value, options: &[Value]) -> Result<Special> {
+        let Value::Symbol(name) = name else {
+            return Err(self.invalid());
+        };
+
+        let mut prefix = None;
+        let mut filter = None;
+        let mut rename = Vec::new();
+
+        for option in options.chunks(2) {
+            match option {
+                [Value::Keyword(keyword), Value::String(string)]
+                    if keyword == "as" =>
+                {
+                    prefix = Some(string.clone());
+                },
+                [Value::Keyword(keyword), names @ Value::List(_)]
+                    if keyword == "only" =>
+                {
+                    filter = Some(ImportFilter::Only(self.names(names)?));
+                },
+                [Value::Keyword(keyword), names @ Value::List(_)]
+                    if keyword == "exclude" =>
+                {
+                    filter = Some(ImportFilter::Exclude(self.names(names)?));
+                },
+                [Value::Keyword(keyword), Value::Map(map)]
+                    if keyword == "rename" =>
+                {
+                    rename = map
+                        .iter()
+                        .map(|(from, to)| {
+                            Ok((self.name(from)?, self.name(to)?))
+                        })
+                        .collect::<Result<_>>()?;
+                },
+                _ => return Err(self.invalid()),
+            }
+        }
+
+        Ok(Special::Import { name: name.clone(), prefix, filter, rename })
+    }
+
+    fn names(&self, value: &Value) -> Result<Vec<String>> {
+        bracket_items(value)
+            .unwrap_or_default()
+            .iter()
+            .map(|value| self.name(value))
+            .collect()
+    }
+
+    fn try_(&self, values: &[Value]) -> Result<Special> {
+        // Returns the rest of `value` if it's a list starting with `name`.
+        fn clause<'v>(value: &'v Value, name: &str) -> Option<&'v [Value]> {
+            match value {
+                Value::List(values) => {
+                    match values.split_first() {
+                        Some((Value::Symbol(head), rest)) if head == name => {
+                            Some(rest)
+                        },
+                        _ => None,
+                    }
+                },
+                _ => None,
+            }
+        }
+
+        let mut body = values;
+        let mut catch = None;
+        let mut finally = None;
+
+        if let Some((last, rest)) = body.split_last() {
+            if let Some(values) = clause(last, "finally") {
+                finally = Some(self.nodes(values)?);
+                body = rest;
+            }
+        }
+
+        if let Some((last, rest)) = body.split_last() {
+            if let Some(values) = clause(last, "catch") {
+                let [name, values @ ..] = values else {
+                    return Err(self.invalid());
+                };
+
+                catch = Some((self.name(name)?, self.nodes(values)?));
+                body = rest;
+            }
+        }
+
+        Ok(Special::Try { body: self.nodes(body)?, catch, finally })
+    }
+}
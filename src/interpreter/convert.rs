@@ -0,0 +1,457 @@
+use std::sync::Arc;
+
+use super::value::{Callable, Map};
+use super::{Arguments, Environment, Interpreter, NativeFunction, Value};
+use crate::error::ErrorKind;
+use crate::parser::parameters::{Parameter, ParameterType, Parameters};
+use crate::Result;
+
+/// Converts a value to a Rust type, for the arguments of native functions.
+pub trait FromValue: Sized {
+    /// The types of the values this converts from. Empty if it converts
+    /// from any value.
+    fn parameter_types() -> Vec<ParameterType>;
+
+    fn from_value(value: Value) -> Result<Self>;
+
+    /// Whether the argument may be left out, in which case it is converted
+    /// from `nil`.
+    fn is_optional() -> bool {
+        false
+    }
+}
+
+/// Converts a Rust type to a value, for the results of native functions.
+/// Fails if there is no value it fits in, e.g. for integers over `i64::MAX`.
+pub trait IntoValue {
+    fn into_value(self) -> Result<Value>;
+}
+
+/// A Rust function whose parameters and result convert to and from values,
+/// so it can be called as a native function.
+///
+/// This is implemented for functions of up to six arguments that return a
+/// `Result`, e.g. `fn(String, Vec<String>) -> Result<String>`.
+pub trait IntoNativeFunction<Args>: Send + Sync + 'static {
+    /// The parameters of the native function, derived from the types of the
+    /// arguments. Trailing `Option`s are optional parameters.
+    fn parameters() -> Parameters;
+
+    fn call_with(&self, arguments: Vec<Value>) -> Result<Value>;
+}
+
+impl NativeFunction {
+    /// Creates a native function from a Rust function, deriving its
+    /// parameters from the types of its arguments.
+    pub fn from_fn<Args, F: IntoNativeFunction<Args>>(
+        name: impl Into<String>,
+        function: F,
+    ) -> Self {
+        Self::with_parameters(name, function, F::parameters())
+    }
+
+    /// Like `from_fn`, but names the parameters `names` instead of `a`, `b`,
+    /// etc.
+    ///
+    /// # Panics
+    ///
+    /// If there isn't exactly one name per parameter.
+    pub fn from_fn_named<Args, F: IntoNativeFunction<Args>>(
+        name: impl Into<String>,
+        function: F,
+        names: &[&str],
+    ) -> Self {
+        let name = name.into();
+        let parameters = F::parameters();
+
+        assert_eq!(
+            parameters.len(),
+            names.len(),
+            "Native function '{name}' should have one name per parameter."
+        );
+
+        let parameters = parameters
+            .iter()
+            .zip(names)
+            .map(|(parameter, name)| parameter.clone().with_name(*name))
+            .collect();
+
+        let parameters = Parameters::new(parameters)
+            .expect("Renaming parameters should keep them valid.");
+
+        Self::with_parameters(name, function, parameters)
+    }
+
+    fn with_parameters<Args, F: IntoNativeFunction<Args>>(
+        name: impl Into<String>,
+        function: F,
+        parameters: Parameters,
+    ) -> Self {
+        NativeFunction::new(
+            name,
+            move |_: &mut Interpreter, arguments: Arguments| {
+                function.call_with(arguments.arguments().to_vec())
+            },
+            parameters,
+        )
+    }
+}
+
+fn invalid_argument<T: FromValue>(value: Value) -> crate::error::Error {
+    ErrorKind::InvalidArgument { expected: T::parameter_types(), actual: value }
+        .into()
+}
+
+impl FromValue for Value {
+    fn parameter_types() -> Vec<ParameterType> {
+        Vec::new()
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        Ok(value)
+    }
+}
+
+impl IntoValue for Value {
+    fn into_value(self) -> Result<Value> {
+        Ok(self)
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Nil)
+    }
+}
+
+impl FromValue for bool {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::Boolean]
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::Boolean(boolean) => Ok(boolean),
+            other => Err(invalid_argument::<Self>(other)),
+        }
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Boolean(self))
+    }
+}
+
+macro_rules! impl_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl FromValue for $integer {
+                fn parameter_types() -> Vec<ParameterType> {
+                    vec![ParameterType::Integer]
+                }
+
+                fn from_value(value: Value) -> Result<Self> {
+                    let Value::Integer(integer) = value else {
+                        return Err(invalid_argument::<Self>(value));
+                    };
+
+                    <$integer>::try_from(integer).map_err(|_| {
+                        ErrorKind::InvalidConversion {
+                            value,
+                            target: stringify!($integer),
+                        }
+                        .into()
+                    })
+                }
+            }
+
+            impl IntoValue for $integer {
+                fn into_value(self) -> Result<Value> {
+                    i64::try_from(self).map(Value::Integer).map_err(|_| {
+                        ErrorKind::IntegerOutOfRange(self.to_string()).into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromValue for f64 {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::Number]
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        value.as_float().ok_or_else(|| invalid_argument::<Self>(value))
+    }
+}
+
+impl IntoValue for f64 {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Float(self))
+    }
+}
+
+impl FromValue for f32 {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::Number]
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_value(value: Value) -> Result<Self> {
+        f64::from_value(value).map(|float| float as f32)
+    }
+}
+
+impl IntoValue for f32 {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Float(self.into()))
+    }
+}
+
+impl FromValue for String {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::String]
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::String(string) => Ok(string),
+            other => Err(invalid_argument::<Self>(other)),
+        }
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::String(self))
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::String(self.to_owned()))
+    }
+}
+
+impl FromValue for Map {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::Map]
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::Map(map) => Ok(map),
+            other => Err(invalid_argument::<Self>(other)),
+        }
+    }
+}
+
+impl IntoValue for Map {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Map(self))
+    }
+}
+
+impl FromValue for Environment {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::Module]
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::Module(environment) => Ok(environment),
+            other => Err(invalid_argument::<Self>(other)),
+        }
+    }
+}
+
+impl IntoValue for Environment {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Module(self))
+    }
+}
+
+impl FromValue for Arc<dyn Callable> {
+    fn parameter_types() -> Vec<ParameterType> {
+        vec![ParameterType::Function]
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::Callable(callable) => Ok(callable),
+            other => Err(invalid_argument::<Self>(other)),
+        }
+    }
+}
+
+impl IntoValue for Arc<dyn Callable> {
+    fn into_value(self) -> Result<Value> {
+        Ok(Value::Callable(self))
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn parameter_types() -> Vec<ParameterType> {
+        let mut types = T::parameter_types();
+
+        if !types.is_empty() {
+            types.push(ParameterType::Nil);
+        }
+
+        types
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::Nil => Ok(None),
+            other => T::from_value(other).map(Some),
+        }
+    }
+
+    fn is_optional() -> bool {
+        true
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Result<Value> {
+        self.map_or(Ok(Value::Nil), IntoValue::into_value)
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn parameter_types() -> Vec<ParameterType> {
+        match T::parameter_types().as_slice() {
+            [ptype] => vec![ParameterType::TypedList(Box::new(ptype.clone()))],
+            _ => vec![ParameterType::List],
+        }
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::List(values) => {
+                values.into_iter().map(T::from_value).collect()
+            },
+            other => Err(invalid_argument::<Self>(other)),
+        }
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Result<Value> {
+        self.into_iter()
+            .map(IntoValue::into_value)
+            .collect::<Result<_>>()
+            .map(Value::List)
+    }
+}
+
+// Tuples convert to and from lists of the same length.
+macro_rules! impl_tuple {
+    ($length:literal; $($element:ident $value:ident),*) => {
+        impl<$($element: FromValue),*> FromValue for ($($element,)*) {
+            fn parameter_types() -> Vec<ParameterType> {
+                vec![ParameterType::List]
+            }
+
+            fn from_value(value: Value) -> Result<Self> {
+                match value {
+                    Value::List(values) if values.len() == $length => {
+                        let mut values = values.into_iter();
+
+                        $(
+                            let $value = $element::from_value(
+                                values.next().expect("length was checked"),
+                            )?;
+                        )*
+
+                        Ok(($($value,)*))
+                    },
+                    Value::List(_) => {
+                        Err(ErrorKind::InvalidConversion {
+                            value,
+                            target: concat!("a list of ", $length, " values"),
+                        }
+                        .into())
+                    },
+                    other => Err(invalid_argument::<Self>(other)),
+                }
+            }
+        }
+
+        impl<$($element: IntoValue),*> IntoValue for ($($element,)*) {
+            fn into_value(self) -> Result<Value> {
+                let ($($value,)*) = self;
+
+                Ok(Value::List(vec![$($value.into_value()?),*]))
+            }
+        }
+    };
+}
+
+impl_tuple!(2; A a, B b);
+impl_tuple!(3; A a, B b, C c);
+impl_tuple!(4; A a, B b, C c, D d);
+
+/// Creates parameters named `a`, `b`, etc. with `types`. Parameters are
+/// optional if they and all parameters after them are.
+fn parameters(types: Vec<(Vec<ParameterType>, bool)>) -> Parameters {
+    let first_optional = types
+        .iter()
+        .rposition(|(_, is_optional)| !is_optional)
+        .map_or(0, |index| index + 1);
+
+    let parameters = types
+        .into_iter()
+        .zip('a'..)
+        .enumerate()
+        .map(|(index, ((types, _), name))| {
+            Parameter::new(name.into(), types, index >= first_optional, false)
+        })
+        .collect();
+
+    Parameters::new(parameters)
+        .expect("Optional parameters should only be at the end.")
+}
+
+macro_rules! impl_into_native_function {
+    ($($argument:ident $value:ident),*) => {
+        impl<F, R, $($argument),*> IntoNativeFunction<($($argument,)*)> for F
+        where
+            F: Fn($($argument),*) -> Result<R> + Send + Sync + 'static,
+            R: IntoValue,
+            $($argument: FromValue,)*
+        {
+            fn parameters() -> Parameters {
+                parameters(vec![
+                    $(($argument::parameter_types(), $argument::is_optional())),*
+                ])
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn call_with(&self, arguments: Vec<Value>) -> Result<Value> {
+                let mut arguments = arguments.into_iter();
+
+                $(
+                    let $value = $argument::from_value(
+                        arguments.next().unwrap_or(Value::Nil),
+                    )?;
+                )*
+
+                self($($value),*).and_then(IntoValue::into_value)
+            }
+        }
+    };
+}
+
+impl_into_native_function!();
+impl_into_native_function!(A a);
+impl_into_native_function!(A a, B b);
+impl_into_native_function!(A a, B b, C c);
+impl_into_native_function!(A a, B b, C c, D d);
+impl_into_native_function!(A a, B b, C c, D d, E e);
+impl_into_native_function!(A a, B b, C c, D d, E e, G g);
@@ -1,61 +1,112 @@
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use super::Value;
 use crate::error::ErrorKind;
 use crate::Result;
 
-#[derive(Debug, Clone)]
+/// A handle to a scope and its parents.
+///
+/// Cloning an `Environment` does not copy the scope, the clone refers to the
+/// same bindings. This allows functions to capture the environment they were
+/// defined in.
+///
+/// A function stored in the environment it captured forms a reference cycle,
+/// so neither is freed when the interpreter is dropped. That's fine for
+/// running a script, but hosts that create many interpreters leak the
+/// functions defined in them.
+#[derive(Clone)]
 pub struct Environment {
+    scope: Arc<RwLock<Scope>>,
+}
+
+#[derive(Default)]
+struct Scope {
     map: HashMap<String, Value>,
-    parent: Option<Box<Environment>>,
+    parent: Option<Environment>,
 }
 
 impl Environment {
     pub fn new() -> Self {
-        Self { map: HashMap::new(), parent: None }
+        Self { scope: Arc::new(RwLock::new(Scope::default())) }
+    }
+
+    pub fn with_parent(parent: Environment) -> Self {
+        Self {
+            scope: Arc::new(RwLock::new(Scope {
+                map: HashMap::new(),
+                parent: Some(parent),
+            })),
+        }
+    }
+
+    /// Returns whether both are the same environment, instead of just having
+    /// the same values.
+    pub(crate) fn ptr_eq(&self, other: &Environment) -> bool {
+        Arc::ptr_eq(&self.scope, &other.scope)
     }
 
-    pub fn merge_values(&mut self, other: Environment) -> Result<()> {
-        for (key, value) in other.map {
+    pub fn merge_values(&self, other: &Environment) -> Result<()> {
+        let values = other.read().map.clone();
+
+        for (key, value) in values {
             self.insert(key, value)?;
         }
 
         Ok(())
     }
 
-    pub fn set_parent(&mut self, environment: Environment) {
-        self.parent.replace(Box::new(environment));
-    }
+    /// Merges the values in `module` that are visible outside it, i.e. those
+    /// that aren't private.
+    pub fn merge_exports(&self, module: &Environment) -> Result<()> {
+        let values = module.read().map.clone();
+
+        for (key, value) in values {
+            if !is_private(&key) {
+                self.insert(key, value)?;
+            }
+        }
 
-    pub fn take_parent(&mut self) -> Option<Environment> {
-        let env = self.parent.take();
+        Ok(())
+    }
 
-        env.map(|e| *e)
+    pub fn parent(&self) -> Option<Environment> {
+        self.read().parent.clone()
     }
 
-    pub fn insert(&mut self, key: String, value: Value) -> Result<()> {
-        if self.has_in_scope(&key) {
-            Err(ErrorKind::SymbolDefined(key).into())
-        } else {
-            self.map.insert(key, value);
-            Ok(())
+    pub fn insert(&self, key: String, value: Value) -> Result<()> {
+        match self.write().map.entry(key) {
+            Entry::Occupied(entry) => {
+                Err(ErrorKind::SymbolDefined(entry.key().clone()).into())
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            },
         }
     }
 
-    pub fn set(&mut self, key: String, value: Value) -> Result<()> {
-        if self.has_in_scope(&key) {
-            let old_value = self.map.insert(key, value);
+    /// Sets an existing binding in the nearest scope that defines `key`.
+    pub fn set(&self, key: String, value: Value) -> Result<()> {
+        let mut scope = self.write();
 
-            debug_assert!(old_value.is_some());
+        if let Some(old_value) = scope.map.get_mut(&key) {
+            *old_value = value;
             Ok(())
+        } else if let Some(parent) = &scope.parent {
+            parent.set(key, value)
         } else {
             Err(ErrorKind::SymbolNotDefined(key).into())
         }
     }
 
-    pub fn get(&self, key: &str) -> Option<&Value> {
-        self.map.get(key).or_else(|| {
-            if let Some(environment) = &self.parent {
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let scope = self.read();
+
+        scope.map.get(key).cloned().or_else(|| {
+            if let Some(environment) = &scope.parent {
                 environment.get(key)
             } else {
                 None
@@ -63,14 +114,9 @@ impl Environment {
         })
     }
 
-    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
-        self.map.get_mut(key).or_else(|| {
-            if let Some(environment) = &mut self.parent {
-                environment.get_mut(key)
-            } else {
-                None
-            }
-        })
+    /// Returns the value of `key` in this scope, without its parents.
+    pub fn get_in_scope(&self, key: &str) -> Option<Value> {
+        self.read().map.get(key).cloned()
     }
 
     pub fn has(&self, key: &str) -> bool {
@@ -78,19 +124,60 @@ impl Environment {
     }
 
     pub fn has_in_scope(&self, key: &str) -> bool {
-        self.map.get(key).is_some()
+        self.read().map.contains_key(key)
+    }
+
+    /// Returns the names bound in this scope and its parents.
+    pub fn names(&self) -> Vec<String> {
+        let scope = self.read();
+
+        let mut names =
+            scope.parent.as_ref().map_or_else(Vec::new, Self::names);
+        names.extend(scope.map.keys().cloned());
+
+        names
+    }
+
+    /// Returns the names bound in this scope that aren't private.
+    pub fn exported_names(&self) -> Vec<String> {
+        self.read().map.keys().filter(|k| !is_private(k)).cloned().collect()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        self.read().map.is_empty()
     }
 
     pub fn len(&self) -> usize {
-        self.map.len()
+        self.read().map.len()
+    }
+
+    /// Formats only the bindings of this scope, without its parents.
+    pub fn format_scope(&self) -> String {
+        let scope = self.read();
+
+        let values =
+            scope.map.iter().map(|(k, v)| (k.as_str(), v)).collect::<Vec<_>>();
+
+        Self::format_table(values)
+    }
+
+    /// Formats the bindings of this scope that aren't private, as they're
+    /// seen from other modules.
+    pub fn format_exports(&self) -> String {
+        let scope = self.read();
+
+        let values = scope
+            .map
+            .iter()
+            .filter(|(k, _)| !is_private(k))
+            .map(|(k, v)| (k.as_str(), v))
+            .collect::<Vec<_>>();
+
+        Self::format_table(values)
     }
 
     pub fn format_table(mut values: Vec<(&str, &Value)>) -> String {
-        values.sort_by(|(l, _), (r, _)| l.cmp(r));
+        values.sort_by_key(|(k, _)| *k);
 
         let key_width =
             values.iter().map(|(k, _)| k.len()).max().unwrap_or_default();
@@ -108,17 +195,15 @@ impl Environment {
         );
 
         for (key, value) in values {
-            if key.starts_with('_') {
-                continue;
-            }
-
-            string += &format!(
-                "| {key:>key_width$} | {:>value_width$} |\n",
+            let _ = writeln!(
+                string,
+                "| {key:>key_width$} | {:>value_width$} |",
                 value.to_string()
             );
         }
 
-        string += &format!(
+        let _ = write!(
+            string,
             "+-{}-+-{}-+",
             "-".repeat(key_width),
             "-".repeat(value_width)
@@ -126,6 +211,20 @@ impl Environment {
 
         string
     }
+
+    fn read(&self) -> RwLockReadGuard<'_, Scope> {
+        self.scope.read().expect("Environment lock should not be poisoned.")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Scope> {
+        self.scope.write().expect("Environment lock should not be poisoned.")
+    }
+}
+
+/// Names starting with an underscore are private to the module that defines
+/// them.
+pub(crate) fn is_private(name: &str) -> bool {
+    name.starts_with('_')
 }
 
 impl Default for Environment {
@@ -134,15 +233,28 @@ impl Default for Environment {
     }
 }
 
+impl std::fmt::Debug for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Functions capture their environment, which may contain the function
+        // itself, so only the keys are printed.
+        let scope = self.read();
+
+        let mut keys = scope.map.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        f.debug_struct("Environment")
+            .field("keys", &keys)
+            .field("parent", &scope.parent)
+            .finish()
+    }
+}
+
 impl std::fmt::Display for Environment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(parent) = &self.parent {
+        if let Some(parent) = &self.read().parent {
             parent.fmt(f)?;
         }
 
-        let values =
-            self.map.iter().map(|(k, v)| (k.as_str(), v)).collect::<Vec<_>>();
-
-        writeln!(f, "{}", Self::format_table(values))
+        writeln!(f, "{}", self.format_scope())
     }
 }
@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+
+use super::environment::is_private;
+use super::{stdlib, Environment, Interpreter, Value};
+use crate::env::TAPR_PATH;
+use crate::error::{Error, ErrorKind};
+use crate::parser::ast::ImportFilter;
+use crate::Result;
+
+impl Interpreter<'_> {
+    /// Reads and interprets the file at `path`. Imports in it are resolved
+    /// relative to its directory.
+    pub fn interpret_file(&mut self, path: &Path) -> Result<Value> {
+        let source = std::fs::read_to_string(path)?;
+
+        self.interpret_at(
+            path.canonicalize()?,
+            &source,
+            &path.to_string_lossy(),
+        )
+    }
+
+    /// Imports the whole standard library into a new scope, so it can be
+    /// shadowed by later definitions.
+    pub fn load_prelude(&mut self) -> Result<()> {
+        let prelude = self.import_module(stdlib::PRELUDE)?;
+
+        let scope = Environment::with_parent(self.environment.clone());
+        scope.merge_exports(&prelude)?;
+
+        self.environment = Environment::with_parent(scope);
+
+        Ok(())
+    }
+
+    /// Evaluates the module `name` in its own environment, or returns it from
+    /// the cache if it was imported before.
+    pub(crate) fn import_module(&mut self, name: &str) -> Result<Environment> {
+        let std_source = stdlib::source(name);
+
+        // Modules of the standard library are cached by their name instead.
+        let path = match std_source {
+            Some(_) => PathBuf::from(name),
+            None if name.starts_with(stdlib::PREFIX) => {
+                return Err(Error::from(ErrorKind::StdModuleNotFound(
+                    name.to_owned(),
+                ))
+                .with_hint(Self::suggest_std_module(name)));
+            },
+            None => self.resolve_import(name)?,
+        };
+
+        if let Some(environment) = self.module_cache.get(&path) {
+            return Ok(environment.clone());
+        }
+
+        if let Some(index) = self.file_stack.iter().position(|p| *p == path) {
+            let chain = self.file_stack[index..]
+                .iter()
+                .chain([&path])
+                .map(|p| p.display().to_string())
+                .collect();
+
+            return Err(ErrorKind::CircularImport(chain).into());
+        }
+
+        // Modules only see the native functions, not the bindings of the
+        // file that imports them.
+        let environment = Environment::with_parent(self.natives.clone());
+
+        self.with_environment(environment.clone(), |intp| {
+            match std_source {
+                Some(source) => intp.interpret_at(path.clone(), source, name),
+                None => intp.interpret_file(&path),
+            }
+        })?;
+
+        self.module_cache.insert(path, environment.clone());
+
+        Ok(environment)
+    }
+
+    /// Binds the names of `module` selected by `filter` in the current scope,
+    /// renamed according to `rename`, and the module itself under `prefix`.
+    ///
+    /// Without a prefix, the module is bound under the name of its file,
+    /// unless `filter` selects the names to import from it directly. An empty
+    /// prefix imports all of its names. Renamed names are imported directly
+    /// in any case.
+    pub(crate) fn bind_import(
+        &self,
+        name: &str,
+        module: &Environment,
+        prefix: Option<&String>,
+        filter: Option<&ImportFilter>,
+        rename: &[(String, String)],
+    ) -> Result<()> {
+        let import_all = prefix.is_some_and(String::is_empty)
+            || matches!(filter, Some(ImportFilter::Exclude(_)));
+
+        let mut names = match filter {
+            Some(ImportFilter::Only(names)) => names.clone(),
+            _ if import_all => module.exported_names(),
+            _ => rename.iter().map(|(from, _)| from.clone()).collect(),
+        };
+
+        let excluded = match filter {
+            Some(ImportFilter::Exclude(excluded)) => excluded.as_slice(),
+            _ => &[],
+        };
+
+        names.retain(|n| !excluded.contains(n));
+        names.sort();
+
+        for selected in excluded.iter().chain(rename.iter().map(|(n, _)| n)) {
+            Self::get_export(name, module, selected)?;
+        }
+
+        let mut bindings = names
+            .iter()
+            .map(|n| Ok((n.clone(), Self::get_export(name, module, n)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        for (from, to) in rename {
+            let Some(binding) = bindings.iter_mut().find(|(n, _)| n == from)
+            else {
+                return Err(ErrorKind::InvalidImport {
+                    name: name.to_owned(),
+                    reason: format!("'{from}' is renamed, but not imported"),
+                }
+                .into());
+            };
+
+            binding.0.clone_from(to);
+        }
+
+        let prefix = match prefix {
+            Some(prefix) => (!prefix.is_empty()).then(|| prefix.clone()),
+            None if filter.is_some() => None,
+            None => {
+                Some(Path::new(name).file_stem().map_or_else(
+                    || name.to_owned(),
+                    |s| s.to_string_lossy().into(),
+                ))
+            },
+        };
+
+        if let Some(prefix) = prefix {
+            bindings.push((prefix, Value::Module(module.clone())));
+        }
+
+        // Check for collisions first, so a failed import binds nothing.
+        for (binding, value) in &bindings {
+            if self.environment.has_in_scope(binding) {
+                let hint = if matches!(value, Value::Module(_)) {
+                    "use `:as` to import the module under another name"
+                } else {
+                    "use `:rename` to import it under another name, or \
+                     `:exclude` to leave it out"
+                };
+
+                return Err(Error::from(ErrorKind::SymbolDefined(
+                    binding.clone(),
+                ))
+                .with_hint(Some(hint.to_owned())));
+            }
+        }
+
+        for (binding, value) in bindings {
+            self.environment.insert(binding, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of `name` in `module`, if it is defined there and
+    /// isn't private.
+    pub(crate) fn get_export(
+        module_name: &str,
+        module: &Environment,
+        name: &str,
+    ) -> Result<Value> {
+        match module.get_in_scope(name) {
+            Some(_) if is_private(name) => {
+                Err(Error::from(ErrorKind::PrivateSymbol {
+                    module: module_name.to_owned(),
+                    name: name.to_owned(),
+                })
+                .with_hint(Some(
+                    "names starting with '_' are only visible inside their \
+                     module"
+                        .to_owned(),
+                )))
+            },
+            Some(value) => Ok(value),
+            None => {
+                Err(Error::from(ErrorKind::SymbolNotDefined(format!(
+                    "{module_name}/{name}"
+                )))
+                .with_hint(Self::suggest_in_module(
+                    module_name,
+                    module,
+                    name,
+                )))
+            },
+        }
+    }
+
+    // Interprets `source`, resolving its imports relative to `path`.
+    fn interpret_at(
+        &mut self,
+        path: PathBuf,
+        source: &str,
+        name: &str,
+    ) -> Result<Value> {
+        self.file_stack.push(path);
+
+        let result = self.interpret(source, name);
+
+        self.file_stack.pop();
+
+        result
+    }
+
+    /// Finds the file for module `name`, first in the directory of the
+    /// importing file, or the working directory if there is none, and then
+    /// in the directories in `TAPR_PATH`.
+    fn resolve_import(&self, name: &str) -> Result<PathBuf> {
+        let mut file_name = PathBuf::from(name);
+
+        if file_name.extension().is_none() {
+            file_name.set_extension("tapr");
+        }
+
+        let importing_directory = match self.file_stack.last() {
+            Some(file) => file.parent().map(Path::to_path_buf),
+            None => std::env::current_dir().ok(),
+        };
+
+        let candidates = importing_directory
+            .into_iter()
+            .chain(TAPR_PATH.iter().cloned())
+            .map(|directory| directory.join(&file_name))
+            .collect::<Vec<_>>();
+
+        for candidate in &candidates {
+            if candidate.is_file() {
+                return Ok(candidate.canonicalize()?);
+            }
+        }
+
+        Err(ErrorKind::ModuleNotFound {
+            name: name.to_owned(),
+            searched: candidates,
+        }
+        .into())
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::code::{
+    code_to_value, datum_to_value, form, value_to_node, QUASIQUOTE, QUOTE,
+};
+use super::value::{Callable, Map};
+use super::{CallableType, Interpreter, Value};
+use crate::error::{Error, ErrorKind};
+use crate::parser::ast::{Node, NodeData, Special};
+use crate::parser::parameters::ParameterType;
+use crate::Result;
+
+impl Interpreter<'_> {
+    /// Expands all macro calls in `node`, except for those in quoted data.
+    pub(crate) fn expand_macros(&mut self, node: &mut Node) -> Result<()> {
+        while let Some(expansion) = self.expand_macro_call(node)? {
+            *node = expansion;
+        }
+
+        if let NodeData::Special(special) = node.data_mut() {
+            match &mut **special {
+                Special::Quote { .. } => return Ok(()),
+                Special::Quasiquote { datum } => {
+                    return self.expand_unquoted(datum)
+                },
+                _ => (),
+            }
+        }
+
+        for child in node.children_mut() {
+            self.expand_macros(child)?;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates a quasiquoted datum, evaluating and inserting any unquoted
+    /// values.
+    ///
+    /// Every `name#` symbol in it is replaced by the same generated symbol, so
+    /// macros can bind names without capturing those of the code they expand.
+    pub(crate) fn eval_quasiquote(&mut self, datum: &Node) -> Result<Value> {
+        self.quasiquote(datum, &mut HashMap::new())
+    }
+
+    fn quasiquote(
+        &mut self,
+        datum: &Node,
+        gensyms: &mut HashMap<String, String>,
+    ) -> Result<Value> {
+        if let NodeData::Map(entries) = datum.data() {
+            let map = entries
+                .iter()
+                .map(|(key, value)| {
+                    Ok((
+                        self.quasiquote(key, gensyms)?,
+                        self.quasiquote(value, gensyms)?,
+                    ))
+                })
+                .collect::<Result<Map>>()?;
+
+            return Ok(Value::Map(map));
+        }
+
+        let NodeData::List { nodes, .. } = datum.data() else {
+            let NodeData::Special(special) = datum.data() else {
+                if let NodeData::Symbol { module: None, value } = datum.data() {
+                    if let Some(prefix) = value.strip_suffix('#') {
+                        let symbol = gensyms
+                            .entry(value.clone())
+                            .or_insert_with(|| gensym(prefix));
+
+                        return Ok(Value::Symbol(symbol.clone()));
+                    }
+                }
+
+                return Ok(datum_to_value(datum));
+            };
+
+            return match &**special {
+                Special::Unquote { value } => value.accept(self),
+                Special::UnquoteSplicing { .. } => {
+                    Err(Error::new(
+                        datum.location(),
+                        ErrorKind::UnquoteSplicingOutsideList,
+                    ))
+                },
+                Special::Quote { datum } => {
+                    Ok(form(QUOTE, vec![self.quasiquote(datum, gensyms)?]))
+                },
+                Special::Quasiquote { datum } => {
+                    Ok(form(QUASIQUOTE, vec![self.quasiquote(datum, gensyms)?]))
+                },
+                _ => Ok(datum_to_value(datum)),
+            };
+        };
+
+        let mut values = Vec::new();
+
+        for node in nodes {
+            if let NodeData::Special(special) = node.data() {
+                if let Special::UnquoteSplicing { value } = &**special {
+                    match value.accept(self)? {
+                        Value::List(items) => values.extend(items),
+                        Value::Nil => (),
+                        other => {
+                            return Err(Error::new(
+                                node.location(),
+                                ErrorKind::InvalidArgument {
+                                    expected: vec![ParameterType::List],
+                                    actual: other,
+                                },
+                            ))
+                        },
+                    }
+
+                    continue;
+                }
+            }
+
+            values.push(self.quasiquote(node, gensyms)?);
+        }
+
+        Ok(Value::List(values))
+    }
+
+    fn expand_unquoted(&mut self, datum: &mut Node) -> Result<()> {
+        if let NodeData::Special(special) = datum.data_mut() {
+            if let Special::Unquote { value }
+            | Special::UnquoteSplicing { value } = &mut **special
+            {
+                return self.expand_macros(value);
+            }
+        }
+
+        for child in datum.children_mut() {
+            self.expand_unquoted(child)?;
+        }
+
+        Ok(())
+    }
+
+    /// If `node` is a call to a macro, calls it with its arguments as data
+    /// and returns the code it produces.
+    fn expand_macro_call(&mut self, node: &Node) -> Result<Option<Node>> {
+        let NodeData::List { literal: false, nodes } = node.data() else {
+            return Ok(None);
+        };
+
+        let Some(NodeData::Symbol { module, value }) =
+            nodes.first().map(Node::data)
+        else {
+            return Ok(None);
+        };
+
+        let Some(callable) = self.get_macro(module.as_ref(), value) else {
+            return Ok(None);
+        };
+
+        let arguments = nodes[1..].iter().map(code_to_value).collect();
+
+        let location = node.location();
+
+        let expansion = self.call_at(callable, arguments, location.clone())?;
+
+        let node = value_to_node(&expansion, &location)
+            .map_err(|e| Self::add_location_to_error(e, &location))?;
+
+        Ok(Some(node))
+    }
+
+    fn get_macro(
+        &self,
+        module: Option<&String>,
+        name: &str,
+    ) -> Option<Arc<dyn Callable>> {
+        let value = if let Some(module) = module {
+            let Some(Value::Module(environment)) = self.environment.get(module)
+            else {
+                return None;
+            };
+
+            environment.get(name)
+        } else {
+            self.environment.get(name)
+        };
+
+        match value {
+            Some(Value::Callable(callable))
+                if matches!(callable.callable_type(), CallableType::Macro) =>
+            {
+                Some(callable)
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Returns a new symbol starting with `prefix`, which is used by `gensym` and
+/// `name#` symbols in quasiquotes.
+pub(crate) fn gensym(prefix: &str) -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("{prefix}__{count}")
+}
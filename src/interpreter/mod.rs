@@ -1,44 +1,72 @@
 mod arguments;
+mod code;
+mod convert;
 mod environment;
+mod import;
+mod macros;
 mod native;
+mod stdlib;
+mod suggestions;
+mod tail;
 mod value;
 
+use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 pub use arguments::Arguments;
-pub use native::{NativeFunction, NativeFunctionImpl};
+pub use convert::{FromValue, IntoNativeFunction, IntoValue};
+pub use native::{
+    NativeFunction, NativeFunctionImpl, NativeModule, NativeModules,
+};
+pub use tail::Tail;
 pub use value::{Callable, CallableType, Value};
 
+use self::code::datum_to_value;
 pub use self::environment::Environment;
-use self::native::get_native_environment;
-use self::value::Function;
-use crate::error::{Error, ErrorKind};
+use self::value::{Function, Macro, Map};
+use crate::error::{Error, ErrorKind, Frame};
 use crate::location::Location;
 use crate::parser::ast;
-use crate::parser::ast::Node;
 use crate::parser::ast::Special::If;
-use crate::parser::parameters::Parameters;
+use crate::parser::ast::{ImportFilter, Node};
+use crate::parser::parameters::{ParameterType, Parameters};
+use crate::parser::pattern::Pattern;
 use crate::visitor::Visitor;
 use crate::Result;
 
 pub struct Interpreter<'i> {
     pub output: Box<dyn Write + 'i>,
     environment: Environment,
+    natives: Environment,
+    call_stack: Vec<Frame>,
+    // Canonical paths of the files being interpreted, innermost last.
+    file_stack: Vec<PathBuf>,
+    module_cache: HashMap<PathBuf, Environment>,
 }
 
-impl<'i> Default for Interpreter<'i> {
+impl Default for Interpreter<'_> {
     fn default() -> Self {
-        let environment = get_native_environment();
+        let environment = NativeModules::builtin()
+            .environment()
+            .expect("Built-in modules should not define a name twice.");
 
-        Self { output: Box::new(std::io::stdout()), environment }
+        Self::new(Box::new(std::io::stdout()), environment)
     }
 }
 
 impl<'i> Interpreter<'i> {
+    /// Creates an interpreter with the native functions in `environment`.
     pub fn new(output: Box<dyn Write + 'i>, environment: Environment) -> Self {
-        Self { output, environment }
+        Self {
+            output,
+            environment: Environment::with_parent(environment.clone()),
+            natives: environment,
+            call_stack: Vec::new(),
+            file_stack: Vec::new(),
+            module_cache: HashMap::new(),
+        }
     }
 
     pub fn interpret(&mut self, source: &str, name: &str) -> Result<Value> {
@@ -47,51 +75,284 @@ impl<'i> Interpreter<'i> {
         node.accept(self)
     }
 
-    pub fn push_environment(&mut self, new_environment: Environment) {
-        let old_environment =
-            std::mem::replace(&mut self.environment, new_environment);
+    /// Returns the value of the global `name`, e.g. a function defined by an
+    /// interpreted script.
+    pub fn get_global(&self, name: &str) -> Result<Value> {
+        self.environment.get(name).ok_or_else(|| {
+            Error::from(ErrorKind::SymbolNotDefined(name.to_owned()))
+                .with_hint(self.suggest_symbol(name))
+        })
+    }
 
-        self.environment.set_parent(old_environment);
+    /// Binds the global `name` to `value`, replacing any previous value.
+    pub fn set_global(&mut self, name: &str, value: Value) -> Result<()> {
+        if self.environment.has_in_scope(name) {
+            self.environment.set(name.to_owned(), value)
+        } else {
+            self.environment.insert(name.to_owned(), value)
+        }
     }
 
-    pub fn pop_environment(&mut self) -> Environment {
-        let parent_environment =
-            self.environment.take_parent().expect("Scope to have parent.");
+    /// Defines a native function in the global environment, where it is
+    /// visible to all code, including imported modules. `parameters` uses
+    /// the same syntax as function parameters, e.g. `"n:number & rest"`.
+    pub fn define_native(
+        &mut self,
+        name: &str,
+        parameters: &str,
+        function: impl Fn(&mut Interpreter, Arguments) -> Result<Value>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<()> {
+        let parameters = Parameters::try_from(parameters)?;
+
+        self.natives.insert(
+            name.to_owned(),
+            NativeFunction::new(name, function, parameters).into(),
+        )
+    }
 
-        std::mem::replace(&mut self.environment, parent_environment)
+    /// Defines a Rust function as a native function in the global
+    /// environment, with parameters derived from the types of its arguments.
+    pub fn define_fn<Args>(
+        &mut self,
+        name: &str,
+        function: impl IntoNativeFunction<Args>,
+    ) -> Result<()> {
+        self.natives.insert(
+            name.to_owned(),
+            NativeFunction::from_fn(name, function).into(),
+        )
     }
 
-    fn get(&self, name: &str, location: Location) -> Result<Value> {
+    /// Runs `function` with `environment` as the current environment,
+    /// restoring the previous environment afterwards, even on error.
+    pub fn with_environment<T>(
+        &mut self,
+        environment: Environment,
+        function: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let old_environment =
+            std::mem::replace(&mut self.environment, environment);
+
+        let result = function(self);
+
+        self.environment = old_environment;
+
+        result
+    }
+
+    fn get(&self, name: &str, location: &Location) -> Result<Value> {
         if let Some(value) = self.environment.get(name) {
-            Ok(value.clone())
+            Ok(value)
         } else {
             Err(Error::new(
-                location,
+                location.clone(),
                 ErrorKind::SymbolNotDefined(name.to_owned()),
-            ))
+            )
+            .with_hint(self.suggest_symbol(name)))
         }
     }
 
-    fn enter_scope(&mut self) {
-        let new_environment = Environment::new();
+    /// Evaluates `values` until one of them is decisive according to
+    /// `is_decisive`, returning that value. The last value is evaluated in
+    /// tail position and `default` is returned if there are no values.
+    fn eval_short_circuit(
+        &mut self,
+        values: &[Node],
+        default: Value,
+        is_decisive: fn(&Value) -> bool,
+    ) -> Result<Tail> {
+        let Some((last, rest)) = values.split_last() else {
+            return Ok(Tail::Value(default));
+        };
 
-        let old_environment =
-            std::mem::replace(&mut self.environment, new_environment);
+        for node in rest {
+            let value = node.accept(self)?;
+
+            if is_decisive(&value) {
+                return Ok(Tail::Value(value));
+            }
+        }
+
+        self.eval_tail(last)
+    }
+
+    /// Evaluates the result of the first clause whose test is truthy, in tail
+    /// position.
+    fn eval_cond(&mut self, clauses: &[(Node, Node)]) -> Result<Tail> {
+        for (test, result) in clauses {
+            if test.accept(self)?.is_truthy() {
+                return self.eval_tail(result);
+            }
+        }
 
-        self.environment.set_parent(old_environment);
+        Ok(Tail::Value(Value::Nil))
     }
 
-    fn exit_scope(&mut self) -> Environment {
-        self.pop_environment()
+    /// Evaluates the result of the first clause whose pattern matches
+    /// `value`, in tail position and in a scope with the pattern's bindings.
+    fn eval_match(
+        &mut self,
+        value: &Node,
+        clauses: &[(Pattern, Node)],
+    ) -> Result<Tail> {
+        let value = value.accept(self)?;
+
+        for (pattern, result) in clauses {
+            if let Some(bindings) = pattern.destructure(&value) {
+                let environment =
+                    Environment::with_parent(self.environment.clone());
+
+                for (name, value) in bindings {
+                    environment.insert(name, value).map_err(|e| {
+                        Self::add_location_to_error(e, &result.location())
+                    })?;
+                }
+
+                return self.with_environment(environment, |intp| {
+                    intp.eval_tail(result)
+                });
+            }
+        }
+
+        Ok(Tail::Value(Value::Nil))
     }
 
-    fn add_location_to_error(mut error: Error, location: Location) -> Error {
-        error.location = error.location.or(Some(location));
+    /// Evaluates a `let` in a new scope, with the last node of its body in
+    /// tail position.
+    fn eval_let(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        location: &Location,
+    ) -> Result<Tail> {
+        self.with_environment(self.environment.clone(), |intp| {
+            for (pattern, node) in bindings {
+                let value = node.accept(intp)?;
+
+                intp.bind_pattern(pattern, value, location)?;
+            }
+
+            intp.eval_body_tail(body)
+        })
+    }
+
+    /// Evaluates a `loop`, rebinding its bindings and evaluating its body
+    /// again whenever the body returns a `recur`.
+    fn eval_loop(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        location: &Location,
+    ) -> Result<Tail> {
+        let outer_environment = self.environment.clone();
+
+        self.with_environment(outer_environment.clone(), |intp| {
+            for (pattern, node) in bindings {
+                let value = node.accept(intp)?;
+
+                intp.bind_pattern(pattern, value, location)?;
+            }
+
+            loop {
+                match intp.eval_body_tail(body)? {
+                    Tail::Recur { arguments, location } => {
+                        if arguments.len() != bindings.len() {
+                            return Err(Error::new(
+                                location,
+                                ErrorKind::WrongAmountOfFixedArgs {
+                                    expected: bindings.len(),
+                                    actual: arguments.len(),
+                                },
+                            ));
+                        }
+
+                        intp.environment = outer_environment.clone();
+
+                        for ((pattern, _), value) in
+                            bindings.iter().zip(arguments)
+                        {
+                            intp.bind_pattern(pattern, value, &location)?;
+                        }
+                    },
+                    tail => return Ok(tail),
+                }
+            }
+        })
+    }
+
+    /// Binds `value` to `pattern` in a new scope, so later bindings can
+    /// shadow earlier ones. Callers are responsible for restoring the
+    /// environment.
+    fn bind_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: Value,
+        location: &Location,
+    ) -> Result<()> {
+        let Some(bindings) = pattern.destructure(&value) else {
+            return Err(Error::new(
+                location.clone(),
+                ErrorKind::PatternMismatch { pattern: pattern.clone(), value },
+            ));
+        };
+
+        self.environment = Environment::with_parent(self.environment.clone());
+
+        for (name, value) in bindings {
+            self.environment
+                .insert(name, value)
+                .map_err(|e| Self::add_location_to_error(e, location))?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts a caught error to a map with its `:kind`, `:message`, thrown
+    /// `:value` and `:location`.
+    #[allow(clippy::cast_possible_wrap)]
+    fn error_to_value(error: Error) -> Value {
+        let keyword = |key: &str| Value::Keyword(key.to_owned());
+
+        let kind = keyword(error.kind.name());
+
+        let (message, value) = match error.kind {
+            ErrorKind::Thrown(value) => (value.to_string(), value),
+            ref kind => (kind.to_string(), Value::Nil),
+        };
+
+        let location = error.location.map_or(Value::Nil, |location| {
+            let source = location.source().map_or(Value::Nil, |source| {
+                Value::String(source.name().to_owned())
+            });
+
+            Value::Map(Map::from_iter([
+                (keyword("source"), source),
+                (keyword("line"), Value::Integer(location.line_no() as i64)),
+                (keyword("col"), Value::Integer(location.col_no() as i64)),
+            ]))
+        });
+
+        Value::Map(Map::from_iter([
+            (keyword("kind"), kind),
+            (keyword("message"), Value::String(message)),
+            (keyword("value"), value),
+            (keyword("location"), location),
+        ]))
+    }
+
+    fn add_location_to_error(mut error: Error, location: &Location) -> Error {
+        if error.location.is_none() {
+            error.location = Some(location.clone());
+        }
+
         error
     }
 }
 
-impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
+impl Visitor<Result<Value>> for Interpreter<'_> {
     fn visit_node(&mut self, node: &Node) -> Result<Value> {
         match node.data() {
             ast::NodeData::Main(nodes) => self.visit_main(nodes),
@@ -103,6 +364,15 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
                     ast::Special::Fn { parameters, body } => {
                         self.visit_fn(parameters, body)
                     },
+                    ast::Special::Defmacro { name, parameters, body } => {
+                        self.visit_defmacro(
+                            name,
+                            parameters,
+                            body,
+                            node.location(),
+                        )
+                    },
+                    ast::Special::Do { body } => self.visit_do(body),
                     ast::Special::Set { name, value } => {
                         self.visit_set(name, value, node.location())
                     },
@@ -110,21 +380,67 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
                     ast::Special::Var { name, value } => {
                         self.visit_var(name, value, node.location())
                     },
-                    ast::Special::Import { name, prefix } => {
-                        self.visit_import(name, prefix.as_ref())
+                    ast::Special::Import { name, prefix, filter, rename } => {
+                        self.visit_import(
+                            name,
+                            prefix.as_ref(),
+                            filter.as_ref(),
+                            rename,
+                            node.location(),
+                        )
+                    },
+                    ast::Special::And { values } => self.visit_and(values),
+                    ast::Special::Or { values } => self.visit_or(values),
+                    ast::Special::NilCoalesce { values } => {
+                        self.visit_nil_coalesce(values)
+                    },
+                    ast::Special::Cond { clauses } => self.visit_cond(clauses),
+                    ast::Special::Match { value, clauses } => {
+                        self.visit_match(value, clauses, node.location())
+                    },
+                    ast::Special::Let { bindings, body } => {
+                        self.visit_let(bindings, body, node.location())
+                    },
+                    ast::Special::Loop { bindings, body } => {
+                        self.visit_loop(bindings, body, node.location())
+                    },
+                    ast::Special::Recur { arguments } => {
+                        self.visit_recur(arguments, node.location())
+                    },
+                    ast::Special::While { condition, body } => {
+                        self.visit_while(condition, body)
+                    },
+                    ast::Special::Dotimes { name, count, body } => {
+                        self.visit_dotimes(name, count, body, node.location())
+                    },
+                    ast::Special::Try { body, catch, finally } => {
+                        self.visit_try(body, catch.as_ref(), finally.as_ref())
+                    },
+                    ast::Special::Quote { datum } => self.visit_quote(datum),
+                    ast::Special::Quasiquote { datum } => {
+                        self.visit_quasiquote(datum)
+                    },
+                    ast::Special::Unquote { value } => {
+                        self.visit_unquote(value, node.location())
+                    },
+                    ast::Special::UnquoteSplicing { value } => {
+                        self.visit_unquote_splicing(value, node.location())
                     },
                 }
             },
             ast::NodeData::List { literal, nodes } => {
                 self.visit_list(*literal, nodes)
             },
+            ast::NodeData::Map(entries) => self.visit_map(entries),
+            ast::NodeData::FString(pieces) => self.visit_fstring(pieces),
             ast::NodeData::Symbol { module, value } => {
                 self.visit_symbol(module.as_ref(), value, node.location())
             },
             ast::NodeData::Keyword(keyword) => {
                 Ok(Value::Keyword(keyword.clone()))
             },
-            ast::NodeData::Number(number) => Ok(Value::Number(*number)),
+            ast::NodeData::Integer(integer) => Ok(Value::Integer(*integer)),
+            ast::NodeData::Float(float) => Ok(Value::Float(*float)),
             ast::NodeData::String(string) => Ok(Value::String(string.clone())),
             ast::NodeData::True => Ok(Value::Boolean(true)),
             ast::NodeData::False => Ok(Value::Boolean(false)),
@@ -133,10 +449,19 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
     }
 
     fn visit_main(&mut self, nodes: &[Node]) -> Result<Value> {
-        let mut values =
-            nodes.iter().map(|n| n.accept(self)).collect::<Result<Vec<_>>>()?;
+        let mut value = Value::Nil;
+
+        // Each node is expanded right before it's evaluated, so it can use
+        // macros defined by the nodes before it.
+        for node in nodes {
+            let mut node = node.clone();
+
+            self.expand_macros(&mut node)?;
 
-        Ok(values.pop().unwrap_or(Value::Nil))
+            value = node.accept(self)?;
+        }
+
+        Ok(value)
     }
 
     fn visit_fn(
@@ -144,11 +469,45 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
         parameters: &Parameters,
         body: &[Node],
     ) -> Result<Value> {
-        let function = Function::new(parameters.clone(), body.to_vec());
+        let function = Function::new(
+            parameters.clone(),
+            body.to_vec(),
+            self.environment.clone(),
+        );
 
         Ok(Value::Callable(Arc::new(function)))
     }
 
+    fn visit_defmacro(
+        &mut self,
+        name: &str,
+        parameters: &Parameters,
+        body: &[Node],
+        location: Location,
+    ) -> Result<Value> {
+        let function = Function::new(
+            parameters.clone(),
+            body.to_vec(),
+            self.environment.clone(),
+        )
+        .with_name(name);
+
+        self.environment
+            .insert(
+                name.to_owned(),
+                Value::Callable(Arc::new(Macro::new(function))),
+            )
+            .map_err(|e| Self::add_location_to_error(e, &location))?;
+
+        Ok(Value::Symbol(name.to_owned()))
+    }
+
+    fn visit_do(&mut self, body: &[Node]) -> Result<Value> {
+        let tail = self.eval_body_tail(body)?;
+
+        self.resolve_tail(tail)
+    }
+
     fn visit_if(
         &mut self,
         condition: &Node,
@@ -164,48 +523,177 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
         }
     }
 
+    fn visit_and(&mut self, values: &[Node]) -> Result<Value> {
+        let tail =
+            self.eval_short_circuit(values, true.into(), Value::is_falsy)?;
+
+        self.resolve_tail(tail)
+    }
+
+    fn visit_or(&mut self, values: &[Node]) -> Result<Value> {
+        let tail =
+            self.eval_short_circuit(values, Value::Nil, Value::is_truthy)?;
+
+        self.resolve_tail(tail)
+    }
+
+    fn visit_nil_coalesce(&mut self, values: &[Node]) -> Result<Value> {
+        let tail = self.eval_short_circuit(values, Value::Nil, |v| {
+            !matches!(v, Value::Nil)
+        })?;
+
+        self.resolve_tail(tail)
+    }
+
+    fn visit_cond(&mut self, clauses: &[(Node, Node)]) -> Result<Value> {
+        let tail = self.eval_cond(clauses)?;
+
+        self.resolve_tail(tail)
+    }
+
+    fn visit_match(
+        &mut self,
+        value: &Node,
+        clauses: &[(Pattern, Node)],
+        _location: Location,
+    ) -> Result<Value> {
+        let tail = self.eval_match(value, clauses)?;
+
+        self.resolve_tail(tail)
+    }
+
     fn visit_import(
         &mut self,
         name: &str,
         prefix: Option<&String>,
+        filter: Option<&ImportFilter>,
+        rename: &[(String, String)],
+        location: Location,
     ) -> Result<Value> {
-        let path = {
-            let path = PathBuf::from(name);
-            if path.extension().is_some() {
-                path
-            } else {
-                path.with_extension("tapr")
-            }
-        };
+        self.import_module(name)
+            .and_then(|module| {
+                self.bind_import(name, &module, prefix, filter, rename)
+            })
+            .map_err(|e| Self::add_location_to_error(e, &location))?;
 
-        let source = std::fs::read_to_string(&path)?;
+        Ok(Value::Nil)
+    }
 
-        let prefix = prefix
-        .cloned()
-        .unwrap_or(path.file_stem().expect("std::fs::read_to_string should guarantee existence of file and file name.").to_string_lossy().to_string());
+    fn visit_let(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        location: Location,
+    ) -> Result<Value> {
+        let tail = self.eval_let(bindings, body, &location)?;
 
-        // If prefix is empty, run everything in the current scope.
-        if !prefix.is_empty() {
-            // Do this to check that prefix isn't inserted yet.
-            self.environment.insert(prefix.clone(), Value::Nil)?;
-            self.enter_scope();
+        self.resolve_tail(tail)
+    }
+
+    fn visit_loop(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        location: Location,
+    ) -> Result<Value> {
+        let tail = self.eval_loop(bindings, body, &location)?;
+
+        self.resolve_tail(tail)
+    }
+
+    fn visit_recur(
+        &mut self,
+        _arguments: &[Node],
+        location: Location,
+    ) -> Result<Value> {
+        Err(Error::new(location, ErrorKind::RecurNotInTailPosition))
+    }
+
+    fn visit_while(
+        &mut self,
+        condition: &Node,
+        body: &[Node],
+    ) -> Result<Value> {
+        while condition.accept(self)?.is_truthy() {
+            let environment =
+                Environment::with_parent(self.environment.clone());
+
+            self.with_environment(environment, |intp| {
+                body.iter().try_for_each(|n| n.accept(intp).map(drop))
+            })?;
         }
 
-        self.interpret(&source, name)?;
+        Ok(Value::Nil)
+    }
+
+    fn visit_dotimes(
+        &mut self,
+        name: &str,
+        count: &Node,
+        body: &[Node],
+        location: Location,
+    ) -> Result<Value> {
+        let count = match count.accept(self)? {
+            Value::Integer(count) => count,
+            other => {
+                return Err(Error::new(location, ErrorKind::InvalidArgument {
+                    expected: vec![ParameterType::Integer],
+                    actual: other,
+                }))
+            },
+        };
 
-        if !prefix.is_empty() {
-            let module_environment = self.exit_scope();
+        for index in 0..count {
+            let environment =
+                Environment::with_parent(self.environment.clone());
 
-            let value = Value::Module(module_environment);
+            environment
+                .insert(name.to_owned(), Value::Integer(index))
+                .map_err(|e| Self::add_location_to_error(e, &location))?;
 
-            self.environment.set(prefix, value).expect(
-                "The module prefix should have already been initialized to Nil",
-            );
+            self.with_environment(environment, |intp| {
+                body.iter().try_for_each(|n| n.accept(intp).map(drop))
+            })?;
         }
 
         Ok(Value::Nil)
     }
 
+    fn visit_try(
+        &mut self,
+        body: &[Node],
+        catch: Option<&(String, Vec<Node>)>,
+        finally: Option<&Vec<Node>>,
+    ) -> Result<Value> {
+        let result =
+            body.iter().try_fold(Value::Nil, |_, node| node.accept(self));
+
+        let result = match (result, catch) {
+            (Err(error), Some((name, catch_body))) => {
+                let environment =
+                    Environment::with_parent(self.environment.clone());
+
+                environment
+                    .insert(name.clone(), Self::error_to_value(error))?;
+
+                self.with_environment(environment, |intp| {
+                    catch_body
+                        .iter()
+                        .try_fold(Value::Nil, |_, node| node.accept(intp))
+                })
+            },
+            (result, _) => result,
+        };
+
+        if let Some(finally_body) = finally {
+            for node in finally_body {
+                node.accept(self)?;
+            }
+        }
+
+        result
+    }
+
     fn visit_set(
         &mut self,
         name: &str,
@@ -216,7 +704,7 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
 
         self.environment
             .set(name.to_owned(), value)
-            .map_err(|e| Self::add_location_to_error(e, location))?;
+            .map_err(|e| Self::add_location_to_error(e, &location))?;
 
         Ok(Value::Symbol(name.to_owned()))
     }
@@ -227,15 +715,57 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
         value: &Node,
         location: Location,
     ) -> Result<Value> {
-        let value = value.accept(self)?;
+        // Functions defined with `defn` or `(var name (fn ...))` are named
+        // after their variable, for backtraces.
+        let value = match value.data() {
+            ast::NodeData::Special(special) => {
+                if let ast::Special::Fn { parameters, body } = &**special {
+                    let function = Function::new(
+                        parameters.clone(),
+                        body.clone(),
+                        self.environment.clone(),
+                    )
+                    .with_name(name);
+
+                    Value::Callable(Arc::new(function))
+                } else {
+                    value.accept(self)?
+                }
+            },
+            _ => value.accept(self)?,
+        };
 
         self.environment
             .insert(name.to_owned(), value)
-            .map_err(|e| Self::add_location_to_error(e, location))?;
+            .map_err(|e| Self::add_location_to_error(e, &location))?;
 
         Ok(Value::Symbol(name.to_owned()))
     }
 
+    fn visit_quote(&mut self, datum: &Node) -> Result<Value> {
+        Ok(datum_to_value(datum))
+    }
+
+    fn visit_quasiquote(&mut self, datum: &Node) -> Result<Value> {
+        self.eval_quasiquote(datum)
+    }
+
+    fn visit_unquote(
+        &mut self,
+        _value: &Node,
+        location: Location,
+    ) -> Result<Value> {
+        Err(Error::new(location, ErrorKind::UnquoteOutsideQuasiquote))
+    }
+
+    fn visit_unquote_splicing(
+        &mut self,
+        _value: &Node,
+        location: Location,
+    ) -> Result<Value> {
+        Err(Error::new(location, ErrorKind::UnquoteOutsideQuasiquote))
+    }
+
     fn visit_list(&mut self, literal: bool, nodes: &[Node]) -> Result<Value> {
         {
             if literal {
@@ -250,33 +780,30 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
                     return Ok(Value::Nil);
                 }
 
-                let node = &nodes[0];
-                let value = nodes[0].accept(self)?;
+                let tail = self.eval_call(nodes)?;
 
-                let Value::Callable(callable) = value else {
-                    return Err(Error::new(
-                        node.location(),
-                        ErrorKind::NotCallable(value),
-                    ));
-                };
+                self.resolve_tail(tail)
+            }
+        }
+    }
 
-                let arguments_vec = nodes[1..]
-                    .iter()
-                    .map(|n| n.accept(self))
-                    .collect::<Result<Vec<_>>>()?;
+    fn visit_map(&mut self, entries: &[(Node, Node)]) -> Result<Value> {
+        let map = entries
+            .iter()
+            .map(|(key, value)| Ok((key.accept(self)?, value.accept(self)?)))
+            .collect::<Result<Map>>()?;
 
-                let parameters = callable.parameters();
+        Ok(Value::Map(map))
+    }
 
-                let arguments = Arguments::new(&parameters, arguments_vec)
-                    .map_err(|e| {
-                        Self::add_location_to_error(e, node.location())
-                    })?;
+    fn visit_fstring(&mut self, pieces: &[Node]) -> Result<Value> {
+        let mut string = String::new();
 
-                callable.call(self, arguments).map_err(|e| {
-                    Self::add_location_to_error(e, node.location())
-                })
-            }
+        for piece in pieces {
+            string.push_str(&piece.accept(self)?.to_string());
         }
+
+        Ok(Value::String(string))
     }
 
     fn visit_symbol(
@@ -286,37 +813,19 @@ impl<'i> Visitor<Result<Value>> for Interpreter<'i> {
         location: Location,
     ) -> Result<Value> {
         if let Some(module) = module {
-            let Value::Module(environment) = self.get(module, location)? else {
+            let Some(Value::Module(environment)) = self.environment.get(module)
+            else {
                 return Err(Error::new(
                     location,
                     ErrorKind::ModuleNotDefined(module.clone()),
-                ));
+                )
+                .with_hint(self.suggest_module(module)));
             };
 
-            self.push_environment(environment);
-
-            let value = self.get(value, location)?;
-
-            self.exit_scope();
-
-            Ok(value)
+            Self::get_export(module, &environment, value)
+                .map_err(|e| Self::add_location_to_error(e, &location))
         } else {
-            self.get(value, location)
+            self.get(value, &location)
         }
     }
-
-    // fn visit_while(&mut self, while_expr: &ast::While) -> Result<Value> {
-    //     let ast::While {
-    //         condition,
-    //         expression,
-    //     } = while_expr;
-
-    //     let mut list = Vec::new();
-
-    //     while condition.accept(self)?.is_truthy() {
-    //         list.push(expression.accept(self)?);
-    //     }
-
-    //     Ok(Value::List(list))
-    // }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use super::environment::Environment;
 use super::value::{Callable, CallableType};
 use super::{Arguments, Interpreter, Parameters, Value};
@@ -5,46 +7,126 @@ use crate::Result;
 
 mod modules;
 
-pub fn get_native_environment() -> Environment {
-    let mut environment = Environment::new();
-
-    for module in modules::get_modules() {
-        if module.is_core_module() {
-            environment.merge_values(module.environment()).unwrap_or_else(
-                |_| panic!("Unable to merge core '{}' module.", module.name()),
-            );
-        } else {
-            environment
-                .insert(module.name().to_owned(), module.environment().into())
-                .unwrap_or_else(|_| {
-                    panic!("Unable to insert '{}' module.", module.name())
-                });
+pub use modules::NativeModule;
+
+/// The native modules an interpreter is created with.
+///
+/// Functions of core modules are available directly, those of other modules
+/// through the name of their module, e.g. `string/join`.
+pub struct NativeModules {
+    modules: Vec<(Box<dyn NativeModule>, bool)>,
+}
+
+impl NativeModules {
+    /// Returns the built-in modules.
+    pub fn builtin() -> Self {
+        let modules = modules::get_modules()
+            .into_iter()
+            .map(|module| {
+                let is_core = module.is_core_module();
+                (module, is_core)
+            })
+            .collect();
+
+        Self { modules }
+    }
+
+    /// Returns no modules at all, not even `println`.
+    pub fn empty() -> Self {
+        Self { modules: Vec::new() }
+    }
+
+    /// Adds `module`, replacing any module with the same name.
+    #[must_use]
+    pub fn with_module(mut self, module: impl NativeModule + 'static) -> Self {
+        self = self.without_module(module.name());
+
+        let is_core = module.is_core_module();
+        self.modules.push((Box::new(module), is_core));
+
+        self
+    }
+
+    /// Removes the module called `name`, if there is one.
+    #[must_use]
+    pub fn without_module(mut self, name: &str) -> Self {
+        self.modules.retain(|(module, _)| module.name() != name);
+        self
+    }
+
+    /// Sets whether the functions of the module called `name` are available
+    /// directly, instead of through the name of the module.
+    #[must_use]
+    pub fn with_core(mut self, name: &str, is_core: bool) -> Self {
+        for (module, module_is_core) in &mut self.modules {
+            if module.name() == name {
+                *module_is_core = is_core;
+            }
         }
+
+        self
     }
 
-    environment
+    pub fn names(&self) -> Vec<&str> {
+        self.modules.iter().map(|(module, _)| module.name()).collect()
+    }
+
+    /// Creates the environment with the functions of all modules, which is
+    /// passed to `Interpreter::new`. Fails if a name is defined twice.
+    pub fn environment(&self) -> Result<Environment> {
+        let environment = Environment::new();
+
+        for (module, is_core) in &self.modules {
+            if *is_core {
+                environment.merge_values(&module.environment())?;
+            } else {
+                environment.insert(
+                    module.name().to_owned(),
+                    module.environment().into(),
+                )?;
+            }
+        }
+
+        Ok(environment)
+    }
 }
 
+impl Default for NativeModules {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// The Rust implementation of a native function, which may capture state.
 pub type NativeFunctionImpl =
-    fn(intp: &mut Interpreter, arguments: Arguments) -> Result<Value>;
+    dyn Fn(&mut Interpreter, Arguments) -> Result<Value> + Send + Sync;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct NativeFunction {
-    name: &'static str,
-    function: NativeFunctionImpl,
+    name: String,
+    function: Arc<NativeFunctionImpl>,
     parameters: Parameters,
 }
 
 impl NativeFunction {
     pub fn new(
-        name: &'static str,
-        function: fn(
-            intp: &mut Interpreter,
-            arguments: Arguments,
-        ) -> Result<Value>,
+        name: impl Into<String>,
+        function: impl Fn(&mut Interpreter, Arguments) -> Result<Value>
+            + Send
+            + Sync
+            + 'static,
         parameters: Parameters,
     ) -> Self {
-        Self { name, function, parameters }
+        Self { name: name.into(), function: Arc::new(function), parameters }
+    }
+}
+
+impl std::fmt::Debug for NativeFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NativeFunction")
+            .field("name", &self.name)
+            .field("parameters", &self.parameters)
+            .finish_non_exhaustive()
     }
 }
 
@@ -67,6 +149,10 @@ impl Callable for NativeFunction {
         self.parameters.len()
     }
 
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
     fn callable_type(&self) -> CallableType {
         CallableType::Native
     }
@@ -1,6 +1,7 @@
-use super::{NativeFunction, NativeFunctionImpl};
+use super::NativeFunction;
 use crate::interpreter::environment::Environment;
-use crate::interpreter::Value;
+use crate::interpreter::{Arguments, Callable, Interpreter, Value};
+use crate::Result;
 
 mod arithmetic;
 mod boolean;
@@ -9,30 +10,39 @@ mod debug;
 mod fs;
 mod io;
 mod list;
+mod map;
 mod number;
 mod string;
 
-pub fn get_modules<'a>() -> Vec<&'a dyn NativeModule> {
+pub fn get_modules() -> Vec<Box<dyn NativeModule>> {
     vec![
-        &core::Core,
-        &arithmetic::Arithmetic,
-        &boolean::Boolean,
-        &debug::Debug,
-        &fs::Fs,
-        &io::Io,
-        &list::List,
-        &number::Number,
-        &string::StringModule,
+        Box::new(core::Core),
+        Box::new(arithmetic::Arithmetic),
+        Box::new(boolean::Boolean),
+        Box::new(debug::Debug),
+        Box::new(fs::Fs),
+        Box::new(io::Io),
+        Box::new(list::List),
+        Box::new(map::Map),
+        Box::new(number::Number),
+        Box::new(string::StringModule),
     ]
 }
 
-pub type NativeFunctionTuple = (&'static str, NativeFunctionImpl, &'static str);
+pub type NativeFunctionTuple = (
+    &'static str,
+    fn(&mut Interpreter, Arguments) -> Result<Value>,
+    &'static str,
+);
 
+/// A module of native functions.
 pub trait NativeModule {
     fn environment(&self) -> Environment;
 
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
+    /// Whether the functions of this module are available without the name
+    /// of the module by default, like `println`.
     fn is_core_module(&self) -> bool;
 }
 
@@ -40,20 +50,31 @@ fn tuples_to_environment(
     tuples: Vec<NativeFunctionTuple>,
     name: &str,
 ) -> Environment {
-    let mut environment = Environment::new();
+    functions_to_environment(
+        tuples.into_iter().map(tuple_to_function).collect(),
+        name,
+    )
+}
+
+fn functions_to_environment(
+    functions: Vec<NativeFunction>,
+    name: &str,
+) -> Environment {
+    let environment = Environment::new();
+
+    for function in functions {
+        let function_name =
+            function.name().expect("Native functions have a name.").to_owned();
 
-    for tuple in tuples {
-        environment
-            .insert(tuple.0.to_owned(), tuple_to_value(tuple))
-            .unwrap_or_else(|_| {
-                panic!("Unable to add {name} functions to environment.")
-            });
+        environment.insert(function_name, function.into()).unwrap_or_else(
+            |_| panic!("Unable to add {name} functions to environment."),
+        );
     }
 
     environment
 }
 
-fn tuple_to_value(tuple: NativeFunctionTuple) -> Value {
+fn tuple_to_function(tuple: NativeFunctionTuple) -> NativeFunction {
     NativeFunction::new(
         tuple.0,
         tuple.1,
@@ -62,5 +83,4 @@ fn tuple_to_value(tuple: NativeFunctionTuple) -> Value {
             .try_into()
             .expect("Native function should have valid parameters-string."),
     )
-    .into()
 }
@@ -1,6 +1,7 @@
 #![allow(clippy::unnecessary_wraps)]
 #![allow(clippy::needless_pass_by_value)]
 use super::{tuples_to_environment, NativeFunctionTuple, NativeModule};
+use crate::error::ErrorKind;
 use crate::interpreter::environment::Environment;
 use crate::interpreter::{Arguments, Interpreter, Value};
 use crate::Result;
@@ -31,71 +32,154 @@ impl NativeModule for Arithmetic {
     }
 }
 
-type BinaryOp = fn(f64, f64) -> f64;
-type UnaryOp = fn(f64) -> f64;
+type IntegerOp = fn(i64, i64) -> Option<i64>;
+type FloatOp = fn(f64, f64) -> f64;
 
-fn variadic(op: BinaryOp, arguments: Arguments) -> Result<Value> {
-    let numbers = arguments.unwrap_numbers();
-
-    let mut iter = numbers.into_iter();
-
-    let mut acc = iter.next().expect("at least two arguments");
+/// The operands of an arithmetic operation. Integers are only converted to
+/// floats if the other operand is a float.
+enum Operands {
+    Integers(i64, i64),
+    Floats(f64, f64),
+}
 
-    for rhs in iter {
-        acc = op(acc, rhs);
+impl Operands {
+    fn new(lhs: &Value, rhs: &Value) -> Self {
+        match (lhs, rhs) {
+            (Value::Integer(lhs), Value::Integer(rhs)) => {
+                Operands::Integers(*lhs, *rhs)
+            },
+            _ => {
+                Operands::Floats(
+                    lhs.as_float().expect("lhs should be a number."),
+                    rhs.as_float().expect("rhs should be a number."),
+                )
+            },
+        }
     }
+}
 
-    Ok(Value::Number(acc))
+fn binary_op(
+    op: &'static str,
+    integer_op: IntegerOp,
+    float_op: FloatOp,
+    lhs: Value,
+    rhs: Value,
+) -> Result<Value> {
+    match Operands::new(&lhs, &rhs) {
+        Operands::Integers(left, right) => {
+            integer_op(left, right).map(Value::Integer).ok_or_else(|| {
+                ErrorKind::IntegerOverflow { op, lhs, rhs }.into()
+            })
+        },
+        Operands::Floats(left, right) => {
+            Ok(Value::Float(float_op(left, right)))
+        },
+    }
 }
 
-fn binary(op: BinaryOp, arguments: Arguments) -> Result<Value> {
-    let [lhs, rhs] = arguments.unwrap_numbers()[..] else { panic!() };
+fn variadic(
+    op: &'static str,
+    integer_op: IntegerOp,
+    float_op: FloatOp,
+    arguments: Arguments,
+) -> Result<Value> {
+    let mut values = arguments.unwrap_from(0).into_iter();
 
-    Ok(Value::Number(op(lhs, rhs)))
-}
+    let mut acc = values.next().expect("at least one argument");
 
-fn unary(op: UnaryOp, arguments: Arguments) -> Result<Value> {
-    let number = arguments.unwrap_numbers()[0];
+    for rhs in values {
+        acc = binary_op(op, integer_op, float_op, acc, rhs)?;
+    }
 
-    Ok(Value::Number(op(number)))
+    Ok(acc)
 }
 
 pub fn add(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    variadic(|lhs, rhs| lhs + rhs, arguments)
+    variadic("add", i64::checked_add, |lhs, rhs| lhs + rhs, arguments)
 }
 
 pub fn subtract(
     _intp: &mut Interpreter,
     arguments: Arguments,
 ) -> Result<Value> {
-    variadic(|lhs, rhs| lhs - rhs, arguments)
+    variadic("subtract", i64::checked_sub, |lhs, rhs| lhs - rhs, arguments)
 }
 
 pub fn multiply(
     _intp: &mut Interpreter,
     arguments: Arguments,
 ) -> Result<Value> {
-    variadic(|lhs, rhs| lhs * rhs, arguments)
+    variadic("multiply", i64::checked_mul, |lhs, rhs| lhs * rhs, arguments)
 }
 
+/// Divides integers exactly if possible, otherwise the result is a float.
 pub fn divide(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    variadic(|lhs, rhs| lhs / rhs, arguments)
+    let mut values = arguments.unwrap_from(0).into_iter();
+
+    let mut acc = values.next().expect("at least one argument");
+
+    for rhs in values {
+        acc = match Operands::new(&acc, &rhs) {
+            Operands::Integers(_, 0) => {
+                return Err(ErrorKind::DivisionByZero(acc).into())
+            },
+            // `checked_rem` is `None` for `i64::MIN / -1`, which overflows.
+            Operands::Integers(left, right)
+                if left.checked_rem(right) == Some(0) =>
+            {
+                Value::Integer(left / right)
+            },
+            Operands::Integers(..) | Operands::Floats(..) => {
+                Value::Float(
+                    acc.as_float().expect("lhs should be a number.")
+                        / rhs.as_float().expect("rhs should be a number."),
+                )
+            },
+        };
+    }
+
+    Ok(acc)
 }
 
 pub fn modulus(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    binary(|lhs, rhs| lhs % rhs, arguments)
+    let lhs = arguments.unwrap(0);
+    let rhs = arguments.unwrap(1);
+
+    if rhs == Value::Integer(0) && matches!(lhs, Value::Integer(_)) {
+        return Err(ErrorKind::DivisionByZero(lhs).into());
+    }
+
+    binary_op(
+        "take the remainder of",
+        i64::checked_rem,
+        |lhs, rhs| lhs % rhs,
+        lhs,
+        rhs,
+    )
 }
 
 pub fn increment(
     _intp: &mut Interpreter,
     arguments: Arguments,
 ) -> Result<Value> {
-    unary(|n| n + 1.0, arguments)
+    binary_op(
+        "add",
+        i64::checked_add,
+        |lhs, rhs| lhs + rhs,
+        arguments.unwrap(0),
+        Value::Integer(1),
+    )
 }
 
 pub fn decrement(
     _intp: &mut Interpreter,
     arguments: Arguments,
 ) -> Result<Value> {
-    unary(|n| n - 1.0, arguments)
+    binary_op(
+        "subtract",
+        i64::checked_sub,
+        |lhs, rhs| lhs - rhs,
+        arguments.unwrap(0),
+        Value::Integer(1),
+    )
 }
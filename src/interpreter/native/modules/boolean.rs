@@ -17,8 +17,8 @@ impl NativeModule for Boolean {
             ("<=", lte, "& b"),
             ("<", lt, "& b"),
             ("!=", ne, "& b"),
-            ("or", or, "& v"),
             ("and", and, "& v"),
+            ("or", or, "& v"),
             ("??", nil_coalesce, "& v"),
         ];
 
@@ -26,7 +26,7 @@ impl NativeModule for Boolean {
     }
 
     fn name(&self) -> &'static str {
-        "arithmetic"
+        "boolean"
     }
 
     fn is_core_module(&self) -> bool {
@@ -34,22 +34,16 @@ impl NativeModule for Boolean {
     }
 }
 
-type BinaryOp = fn(Value, Value) -> bool;
+type BinaryOp = fn(&Value, &Value) -> bool;
 type UnaryOp = fn(Value) -> bool;
 
+/// Returns whether `op` holds for every pair of adjacent arguments.
 fn variadic(op: BinaryOp, arguments: Arguments) -> Result<Value> {
     let values = arguments.unwrap_from(0);
 
-    let mut acc = true;
-
-    for window in values.windows(2) {
-        let lhs = window[0].clone();
-        let rhs = window[1].clone();
-
-        acc = op(lhs, rhs);
-    }
+    let result = values.windows(2).all(|window| op(&window[0], &window[1]));
 
-    Ok(Value::Boolean(acc))
+    Ok(Value::Boolean(result))
 }
 
 fn unary(op: UnaryOp, arguments: Arguments) -> Result<Value> {
@@ -63,11 +57,11 @@ pub fn not(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
 }
 
 pub fn gt(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    variadic(|lhs, rhs| lhs < rhs, arguments)
+    variadic(|lhs, rhs| lhs > rhs, arguments)
 }
 
 pub fn gte(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    variadic(|lhs, rhs| lhs <= rhs, arguments)
+    variadic(|lhs, rhs| lhs >= rhs, arguments)
 }
 
 pub fn eq(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
@@ -75,55 +69,49 @@ pub fn eq(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
 }
 
 pub fn lte(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    variadic(|lhs, rhs| lhs >= rhs, arguments)
+    variadic(|lhs, rhs| lhs <= rhs, arguments)
 }
 
 pub fn lt(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    variadic(|lhs, rhs| lhs > rhs, arguments)
+    variadic(|lhs, rhs| lhs < rhs, arguments)
 }
 
 pub fn ne(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
     variadic(|lhs, rhs| lhs != rhs, arguments)
 }
 
-pub fn or(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let values = arguments.unwrap_from(0);
-    let last_index = values.len() - 1;
+/// Returns the first value for which `stop` holds, or the last value. Unlike
+/// the special forms they back, the `and`, `or` and `??` functions evaluate
+/// all of their arguments, but can be passed to e.g. `apply`.
+fn short_circuit(
+    arguments: Arguments,
+    empty: Value,
+    stop: fn(&Value) -> bool,
+) -> Result<Value> {
+    let mut result = empty;
 
-    for (i, argument) in values.into_iter().enumerate() {
-        if argument.is_truthy() || i == last_index {
-            return Ok(argument);
+    for value in arguments.unwrap_from(0) {
+        result = value;
+
+        if stop(&result) {
+            break;
         }
     }
 
-    unreachable!()
+    Ok(result)
 }
 
 pub fn and(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let values = arguments.unwrap_from(0);
-    let last_index = values.len() - 1;
-
-    for (i, argument) in values.into_iter().enumerate() {
-        if argument.is_falsy() || i == last_index {
-            return Ok(argument);
-        }
-    }
+    short_circuit(arguments, Value::Boolean(true), Value::is_falsy)
+}
 
-    unreachable!()
+pub fn or(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    short_circuit(arguments, Value::Nil, Value::is_truthy)
 }
 
 pub fn nil_coalesce(
     _intp: &mut Interpreter,
     arguments: Arguments,
 ) -> Result<Value> {
-    let values = arguments.unwrap_from(0);
-    let last_index = values.len() - 1;
-
-    for (i, argument) in values.into_iter().enumerate() {
-        if !matches!(argument, Value::Nil) || i == last_index {
-            return Ok(argument);
-        }
-    }
-
-    unreachable!()
+    short_circuit(arguments, Value::Nil, |v| !matches!(v, Value::Nil))
 }
@@ -2,8 +2,9 @@
 #![allow(clippy::unnecessary_wraps)]
 
 use super::{tuples_to_environment, NativeFunctionTuple, NativeModule};
+use crate::error::ErrorKind;
 use crate::interpreter::environment::Environment;
-use crate::interpreter::{Arguments, Interpreter, Value};
+use crate::interpreter::{macros, Arguments, Interpreter, Value};
 use crate::Result;
 
 pub struct Core;
@@ -13,7 +14,14 @@ impl NativeModule for Core {
         let tuples: Vec<NativeFunctionTuple> = vec![
             ("println", println, "& s"),
             ("print", print, "& s"),
+            ("str", str, "& values"),
             ("is-nil", is_nil, "v"),
+            ("gensym", gensym, "&opt prefix:string"),
+            ("symbol", symbol, "name:string|symbol"),
+            ("symbol?", is_symbol, "v"),
+            ("name", name, "v:string|symbol|keyword"),
+            ("throw", throw, "value"),
+            ("apply", apply, "f:function arguments:list"),
         ];
 
         tuples_to_environment(tuples, self.name())
@@ -46,8 +54,72 @@ fn print(intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
     Ok(Value::Nil)
 }
 
+/// Concatenates the printed values of all arguments, like an f-string.
+fn str(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let string = arguments
+        .arguments()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<String>();
+
+    Ok(Value::String(string))
+}
+
 fn is_nil(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
     let argument = arguments.unwrap(0);
 
     Ok(matches!(argument, Value::Nil).into())
 }
+
+/// Returns a new symbol, which macros can bind without capturing symbols from
+/// the code they expand.
+fn gensym(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let prefix = match arguments.arguments().first() {
+        Some(Value::String(prefix)) => prefix.as_str(),
+        _ => "G",
+    };
+
+    Ok(Value::Symbol(macros::gensym(prefix)))
+}
+
+fn symbol(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    match arguments.unwrap(0) {
+        Value::String(name) | Value::Symbol(name) => Ok(Value::Symbol(name)),
+        _ => unreachable!("Arguments should have been type-checked."),
+    }
+}
+
+fn is_symbol(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let argument = arguments.unwrap(0);
+
+    Ok(matches!(argument, Value::Symbol(_)).into())
+}
+
+/// Returns the name of a symbol without its module, or the name of a keyword
+/// without its colon.
+fn name(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let name = match arguments.unwrap(0) {
+        Value::String(string) | Value::Keyword(string) => string,
+        Value::Symbol(symbol) => {
+            symbol
+                .rsplit_once('/')
+                .map_or(symbol.as_str(), |(_, name)| name)
+                .to_owned()
+        },
+        _ => unreachable!("Arguments should have been type-checked."),
+    };
+
+    Ok(Value::String(name))
+}
+
+fn throw(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    Err(ErrorKind::Thrown(arguments.unwrap(0)).into())
+}
+
+/// Calls `f` with the values in `arguments` as its arguments.
+fn apply(intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let function = arguments.unwrap(0);
+    let values = arguments.unwrap_list(1);
+
+    intp.call(&function, values)
+}
@@ -33,7 +33,7 @@ fn env(intp: &mut Interpreter, _arguments: Arguments) -> Result<Value> {
 fn lsmod(intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
     let environment = arguments.unwrap_module(0);
 
-    writeln!(intp.output, "{environment}")?;
+    writeln!(intp.output, "{}", environment.format_exports())?;
 
     Ok(Value::Nil)
 }
@@ -13,10 +13,17 @@ impl NativeModule for List {
         let tuples: Vec<NativeFunctionTuple> = vec![
             ("head", head, "l:list"),
             ("tail", tail, "l:list"),
-            ("push", push, "l:list"),
+            ("push", push, "l:list & values"),
             ("filter", filter, "f:function l:list"),
             ("map", map, "f:function l:list"),
             ("reduce", reduce, "f:function init l:list"),
+            ("len", len, "l:list"),
+            ("take", take, "n:int l:list"),
+            ("drop", drop, "n:int l:list"),
+            ("concat", concat, "& lists:list"),
+            ("reverse", reverse, "l:list"),
+            ("zip", zip, "l:list r:list"),
+            ("range", range, "start:int &opt end:int|nil"),
         ];
 
         tuples_to_environment(tuples, self.name())
@@ -107,3 +114,82 @@ fn map(intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
 
     Ok(Value::List(output))
 }
+
+fn len(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let length = arguments.unwrap_list(0).len();
+
+    Ok(Value::Integer(length.try_into().unwrap_or(i64::MAX)))
+}
+
+/// Returns the number of elements `n` stands for, which is zero if it is
+/// negative.
+fn count_argument(arguments: &Arguments, index: usize) -> usize {
+    arguments.unwrap_integer(index).try_into().unwrap_or_default()
+}
+
+/// Returns the first `n` elements of a list.
+fn take(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let n = count_argument(&arguments, 0);
+    let mut list = arguments.unwrap_list(1);
+
+    list.truncate(n);
+
+    Ok(Value::List(list))
+}
+
+/// Returns all but the first `n` elements of a list.
+fn drop(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let n = count_argument(&arguments, 0);
+    let list = arguments.unwrap_list(1);
+
+    Ok(Value::List(list.into_iter().skip(n).collect()))
+}
+
+fn concat(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let output = arguments
+        .unwrap_from(0)
+        .into_iter()
+        .flat_map(|list| {
+            let Value::List(values) = list else {
+                unreachable!("Rest parameter should only contain lists.")
+            };
+
+            values
+        })
+        .collect();
+
+    Ok(Value::List(output))
+}
+
+fn reverse(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let mut list = arguments.unwrap_list(0);
+
+    list.reverse();
+
+    Ok(Value::List(list))
+}
+
+/// Pairs up the elements of two lists, up to the length of the shortest one.
+fn zip(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let lhs = arguments.unwrap_list(0);
+    let rhs = arguments.unwrap_list(1);
+
+    let output = lhs
+        .into_iter()
+        .zip(rhs)
+        .map(|(lhs, rhs)| Value::List(vec![lhs, rhs]))
+        .collect();
+
+    Ok(Value::List(output))
+}
+
+/// Returns the integers from `start` up to, but not including, `end`. With
+/// one argument, counts from zero.
+fn range(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let (start, end) = match arguments.arguments().get(1) {
+        Some(Value::Integer(end)) => (arguments.unwrap_integer(0), *end),
+        _ => (0, arguments.unwrap_integer(0)),
+    };
+
+    Ok(Value::List((start..end).map(Value::Integer).collect()))
+}
@@ -0,0 +1,107 @@
+#![allow(clippy::unnecessary_wraps)]
+#![allow(clippy::needless_pass_by_value)]
+
+use super::{tuples_to_environment, NativeFunctionTuple, NativeModule};
+use crate::interpreter::environment::Environment;
+use crate::interpreter::{Arguments, Interpreter, Value};
+use crate::Result;
+
+pub struct Map;
+
+impl NativeModule for Map {
+    fn environment(&self) -> Environment {
+        let tuples: Vec<NativeFunctionTuple> = vec![
+            ("get", get, "m:map key &opt default"),
+            ("assoc", assoc, "m:map key value"),
+            ("dissoc", dissoc, "m:map & keys"),
+            ("keys", keys, "m:map"),
+            ("vals", vals, "m:map"),
+            ("merge", merge, "& maps:map"),
+            ("contains?", contains, "m:map key"),
+            ("update", update, "m:map key f:function & args"),
+        ];
+
+        tuples_to_environment(tuples, self.name())
+    }
+
+    fn name(&self) -> &'static str {
+        "map"
+    }
+
+    fn is_core_module(&self) -> bool {
+        false
+    }
+}
+
+fn get(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let map = arguments.unwrap_map(0);
+    let key = arguments.unwrap(1);
+
+    let value = map
+        .get(&key)
+        .or_else(|| arguments.arguments().get(2))
+        .cloned()
+        .unwrap_or(Value::Nil);
+
+    Ok(value)
+}
+
+fn assoc(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let mut map = arguments.unwrap_map(0);
+
+    map.insert(arguments.unwrap(1), arguments.unwrap(2));
+
+    Ok(Value::Map(map))
+}
+
+fn dissoc(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let mut map = arguments.unwrap_map(0);
+
+    for key in &arguments.arguments()[1..] {
+        map.remove(key);
+    }
+
+    Ok(Value::Map(map))
+}
+
+fn keys(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let map = arguments.unwrap_map(0);
+
+    Ok(Value::List(map.keys().cloned().collect()))
+}
+
+fn vals(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let map = arguments.unwrap_map(0);
+
+    Ok(Value::List(map.values().cloned().collect()))
+}
+
+fn merge(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let map = (0..arguments.len())
+        .flat_map(|index| arguments.unwrap_map(index))
+        .collect();
+
+    Ok(Value::Map(map))
+}
+
+fn contains(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let map = arguments.unwrap_map(0);
+
+    Ok(map.contains_key(&arguments.unwrap(1)).into())
+}
+
+fn update(intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let mut map = arguments.unwrap_map(0);
+    let key = arguments.unwrap(1);
+    let function = arguments.unwrap(2);
+
+    let old_value = map.get(&key).cloned().unwrap_or(Value::Nil);
+
+    let values = [vec![old_value], arguments.unwrap_from(3)].concat();
+
+    let new_value = intp.call(&function, values)?;
+
+    map.insert(key, new_value);
+
+    Ok(Value::Map(map))
+}
@@ -1,8 +1,7 @@
 #![allow(clippy::unnecessary_wraps)]
 #![allow(clippy::needless_pass_by_value)]
 
-use conv::prelude::*;
-
+use super::string::FormatSpec;
 use super::{tuples_to_environment, NativeFunctionTuple, NativeModule};
 use crate::error::ErrorKind;
 use crate::interpreter::environment::Environment;
@@ -15,7 +14,9 @@ impl NativeModule for Number {
     fn environment(&self) -> Environment {
         let tuples: Vec<NativeFunctionTuple> = vec![
             ("parse", parse, "s:string"),
-            ("align", align, "width:number n:number"),
+            ("align", align, "width:int n:number"),
+            ("int", int, "n:number"),
+            ("float", float, "n:number"),
         ];
 
         tuples_to_environment(tuples, self.name())
@@ -30,29 +31,55 @@ impl NativeModule for Number {
     }
 }
 
+/// Pads `n` with zeros to `width`, like `(string/format "{:0>width}" n)`.
 pub fn align(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let f_width = arguments.unwrap_number(0);
-    let n = arguments.unwrap_number(1);
-
-    if f_width.fract() != 0.0 {
-        return Err(ErrorKind::InvalidInteger(f_width).into());
-    }
+    let width = arguments.unwrap_integer(0);
+    let n = arguments.unwrap(1);
 
-    let width: usize = f_width
-        .round()
-        .approx()
-        .map_err(|_| ErrorKind::InvalidInteger(f_width))?;
+    let width = usize::try_from(width)
+        .map_err(|_| ErrorKind::NegativeInteger(width))?;
 
-    Ok(format!("{n:0>width$}").into())
+    Ok(FormatSpec::right_aligned('0', width).apply(&n).into())
 }
 
 pub fn parse(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
     let string = arguments.unwrap_string(0);
 
-    let result: Result<f64> = string
-        .trim()
+    let trimmed = string.trim();
+
+    if let Ok(integer) = trimmed.parse() {
+        return Ok(Value::Integer(integer));
+    }
+
+    let result: Result<f64> = trimmed
         .parse()
-        .map_err(|_| ErrorKind::ParseNumberError(string).into());
+        .map_err(|_| ErrorKind::ParseNumberError(string.clone()).into());
+
+    Ok(Value::Float(result?))
+}
+
+/// Converts a number to an integer, truncating floats towards zero.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn int(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let value = arguments.unwrap(0);
+
+    if let Value::Integer(integer) = value {
+        return Ok(Value::Integer(integer));
+    }
+
+    let float = arguments.unwrap_number(0);
+
+    // `i64::MAX as f64` rounds up to 2^63, which doesn't fit in an i64.
+    if !float.is_finite() || float < i64::MIN as f64 || float >= i64::MAX as f64
+    {
+        return Err(
+            ErrorKind::InvalidConversion { value, target: "int" }.into()
+        );
+    }
+
+    Ok(Value::Integer(float as i64))
+}
 
-    Ok(Value::Number(result?))
+pub fn float(_intp: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    Ok(Value::Float(arguments.unwrap_number(0)))
 }
@@ -1,25 +1,35 @@
 #![allow(clippy::unnecessary_wraps)]
 #![allow(clippy::needless_pass_by_value)]
 
-use super::{tuples_to_environment, NativeFunctionTuple, NativeModule};
+use super::{functions_to_environment, tuple_to_function, NativeModule};
 use crate::error::ErrorKind;
 use crate::interpreter::environment::Environment;
-use crate::interpreter::{Arguments, Interpreter, Value};
-use crate::{ParameterType, Result};
+use crate::interpreter::{Arguments, Interpreter, NativeFunction, Value};
+use crate::Result;
 
 pub struct StringModule;
 
 impl NativeModule for StringModule {
     fn environment(&self) -> Environment {
-        let tuples: Vec<NativeFunctionTuple> = vec![
-            ("len", len, "s:string"),
-            ("join", join, "separator:string l:list"),
-            ("join-not-nil", join_not_nil, "separator:string l:list"),
-            ("trim", trim, "s:string"),
-            ("split", split, "separator:string string:string"),
+        let functions = vec![
+            NativeFunction::from_fn_named("len", len, &["string"]),
+            NativeFunction::from_fn_named("join", join, &[
+                "separator",
+                "strings",
+            ]),
+            NativeFunction::from_fn_named("join-not-nil", join_not_nil, &[
+                "separator",
+                "values",
+            ]),
+            NativeFunction::from_fn_named("trim", trim, &["string"]),
+            NativeFunction::from_fn_named("split", split, &[
+                "separator",
+                "string",
+            ]),
+            tuple_to_function(("format", format, "format:string & values")),
         ];
 
-        tuples_to_environment(tuples, self.name())
+        functions_to_environment(functions, self.name())
     }
 
     fn name(&self) -> &'static str {
@@ -31,83 +41,324 @@ impl NativeModule for StringModule {
     }
 }
 
-type UnaryOp = fn(&str) -> String;
+fn len(string: String) -> Result<usize> {
+    Ok(string.len())
+}
+
+fn join(separator: String, strings: Vec<String>) -> Result<String> {
+    Ok(strings.join(&separator))
+}
 
-fn unary(op: UnaryOp, arguments: Arguments) -> Result<Value> {
-    let string = arguments.unwrap_string(0);
+/// Joins the strings in `values`, skipping `nil`. Returns `nil` if there are
+/// no strings.
+fn join_not_nil(
+    separator: String,
+    values: Vec<Option<String>>,
+) -> Result<Option<String>> {
+    let strings = values.into_iter().flatten().collect::<Vec<_>>();
 
-    Ok(Value::String(op(&string)))
+    Ok((!strings.is_empty()).then(|| strings.join(&separator)))
 }
 
-fn len(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let string = arguments.unwrap_string(0);
+fn trim(string: String) -> Result<String> {
+    Ok(string.trim().to_owned())
+}
 
-    #[allow(clippy::cast_precision_loss)]
-    Ok(Value::Number(string.len() as f64))
+fn split(separator: String, string: String) -> Result<Vec<String>> {
+    Ok(string.split(&separator).map(ToOwned::to_owned).collect())
 }
 
-fn join(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let separator = arguments.unwrap_string(0);
-    let values = arguments.unwrap_list(1);
-
-    let strings = values
-        .into_iter()
-        .map(|value| {
-            if let Value::String(s) = value {
-                Ok(s)
-            } else {
-                Err(ErrorKind::InvalidArgument {
-                    expected: vec![ParameterType::String],
-                    actual: value,
-                }
-                .into())
-            }
-        })
-        .collect::<Result<Vec<_>>>()?;
+/// Formats `values` according to `format`, e.g. `(format "{:>5}" 1)`.
+///
+/// Placeholders are `{}` for the next value, `{0}` for a value by index or
+/// `{name}` for the `:name` key of a map passed as the last value. After a
+/// `:`, a placeholder takes `[[fill]align][0][width][.precision]`, like
+/// Rust's `format!`. Use `{{` and `}}` for literal braces.
+fn format(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
+    let format = arguments.unwrap_string(0);
+    let values = arguments.unwrap_from(1);
 
-    Ok(Value::String(strings.join(&separator)))
+    Ok(Value::String(format_values(&format, &values)?))
 }
 
-fn join_not_nil(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let separator = arguments.unwrap_string(0);
-    let values = arguments.unwrap_list(1);
-
-    let strings = values
-        .into_iter()
-        .filter_map(|value| {
-            match value {
-                Value::String(s) => Some(Ok(s)),
-                Value::Nil => None,
-                other => {
-                    Some(Err(ErrorKind::InvalidArgument {
-                        expected: vec![ParameterType::String],
-                        actual: other,
+fn format_values(format: &str, values: &[Value]) -> Result<String> {
+    let invalid = |reason| {
+        ErrorKind::InvalidFormatString { format: format.to_owned(), reason }
+    };
+
+    let mut output = String::new();
+    let mut chars = format.chars().peekable();
+    let mut next_index = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            },
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            },
+            '{' => {
+                let mut placeholder = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err(invalid("unclosed '{'").into()),
                     }
-                    .into()))
-                },
-            }
-        })
-        .collect::<Result<Vec<_>>>()?;
+                }
+
+                let (argument, spec) =
+                    placeholder.split_once(':').unwrap_or((&placeholder, ""));
+
+                let value = if argument.is_empty() {
+                    next_index += 1;
+                    values.get(next_index - 1)
+                } else if let Ok(index) = argument.parse::<usize>() {
+                    values.get(index)
+                } else {
+                    named_value(values, argument)
+                }
+                .ok_or_else(|| {
+                    ErrorKind::FormatArgumentMissing(placeholder.clone())
+                })?;
+
+                let spec = FormatSpec::parse(spec).ok_or_else(|| {
+                    invalid("expected '[[fill]align][0][width][.precision]'")
+                })?;
 
-    if strings.is_empty() {
-        Ok(Value::Nil)
-    } else {
-        Ok(Value::String(strings.join(&separator)))
+                output.push_str(&spec.apply(value));
+            },
+            '}' => return Err(invalid("unmatched '}'").into()),
+            c => output.push(c),
+        }
     }
+
+    Ok(output)
+}
+
+fn named_value<'a>(values: &'a [Value], name: &str) -> Option<&'a Value> {
+    let Some(Value::Map(map)) = values.last() else {
+        return None;
+    };
+
+    map.get(&Value::Keyword(name.to_owned()))
+        .or_else(|| map.get(&Value::String(name.to_owned())))
 }
 
-fn trim(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    unary(|s| s.trim().to_owned(), arguments)
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '<' => Some(Align::Left),
+            '^' => Some(Align::Center),
+            '>' => Some(Align::Right),
+            _ => None,
+        }
+    }
+}
+
+pub(super) struct FormatSpec {
+    fill: char,
+    align: Option<Align>,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl FormatSpec {
+    /// Returns the spec `{:F>W}`, which right-aligns a value in `width`
+    /// characters padded with `fill`.
+    pub(super) fn right_aligned(fill: char, width: usize) -> Self {
+        Self {
+            fill,
+            align: Some(Align::Right),
+            zero: false,
+            width,
+            precision: None,
+        }
+    }
+
+    fn parse(spec: &str) -> Option<Self> {
+        let chars = spec.chars().collect::<Vec<_>>();
+        let mut rest = &chars[..];
+
+        let mut fill = ' ';
+        let mut align = None;
+
+        if let [c, a, ..] = rest {
+            if let Some(a) = Align::from_char(*a) {
+                fill = *c;
+                align = Some(a);
+                rest = &rest[2..];
+            }
+        }
+
+        if align.is_none() {
+            if let Some(a) = rest.first().copied().and_then(Align::from_char) {
+                align = Some(a);
+                rest = &rest[1..];
+            }
+        }
+
+        let zero = rest.first() == Some(&'0');
+
+        if zero {
+            rest = &rest[1..];
+        }
+
+        let (width, rest) = Self::parse_integer(rest);
+
+        let (precision, rest) = match rest {
+            ['.', rest @ ..] => {
+                let (precision, rest) = Self::parse_integer(rest);
+
+                (Some(precision?), rest)
+            },
+            rest => (None, rest),
+        };
+
+        if !rest.is_empty() {
+            return None;
+        }
+
+        Some(Self { fill, align, zero, width: width.unwrap_or(0), precision })
+    }
+
+    fn parse_integer(chars: &[char]) -> (Option<usize>, &[char]) {
+        let length = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+
+        let integer = chars[..length].iter().collect::<String>().parse().ok();
+
+        (integer, &chars[length..])
+    }
+
+    pub(super) fn apply(&self, value: &Value) -> String {
+        let is_number = matches!(value, Value::Integer(_) | Value::Float(_));
+
+        let text = match (value, self.precision) {
+            (Value::String(string), Some(precision)) => {
+                string.chars().take(precision).collect()
+            },
+            (value, Some(precision)) if is_number => {
+                let float =
+                    value.as_float().expect("Value should be a number.");
+
+                format!("{float:.precision$}")
+            },
+            (value, _) => value.to_string(),
+        };
+
+        let padding = self.width.saturating_sub(text.chars().count());
+
+        // Zero-padding goes between the sign and the digits.
+        if self.zero && is_number && self.align.is_none() {
+            let (sign, digits) = match text.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", text.as_str()),
+            };
+
+            return format!("{sign}{}{digits}", "0".repeat(padding));
+        }
+
+        let fill = |n| self.fill.to_string().repeat(n);
+
+        let align = self.align.unwrap_or(if is_number {
+            Align::Right
+        } else {
+            Align::Left
+        });
+
+        match align {
+            Align::Left => format!("{text}{}", fill(padding)),
+            Align::Center => {
+                format!(
+                    "{}{text}{}",
+                    fill(padding / 2),
+                    fill(padding - padding / 2)
+                )
+            },
+            Align::Right => format!("{}{text}", fill(padding)),
+        }
+    }
 }
 
-fn split(_: &mut Interpreter, arguments: Arguments) -> Result<Value> {
-    let separator = arguments.unwrap_string(0);
-    let string = arguments.unwrap_string(1);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let values = string
-        .split(&separator)
-        .map(|s| Value::String(s.to_owned()))
-        .collect::<Vec<_>>();
+    fn apply(spec: &str, value: Value) -> Option<String> {
+        FormatSpec::parse(spec).map(|spec| spec.apply(&value))
+    }
 
-    Ok(Value::List(values))
+    #[test]
+    fn default_alignment_depends_on_type() {
+        assert_eq!(apply("5", Value::Integer(42)).unwrap(), "   42");
+        assert_eq!(apply("5", "ab".into()).unwrap(), "ab   ");
+    }
+
+    #[test]
+    fn align_and_fill() {
+        assert_eq!(apply("<5", Value::Integer(42)).unwrap(), "42   ");
+        assert_eq!(apply("^6", "ab".into()).unwrap(), "  ab  ");
+        assert_eq!(apply("^5", "ab".into()).unwrap(), " ab  ");
+        assert_eq!(apply("*>5", "ab".into()).unwrap(), "***ab");
+        assert_eq!(apply("0<4", Value::Integer(7)).unwrap(), "7000");
+    }
+
+    #[test]
+    fn fill_may_be_an_align_character() {
+        assert_eq!(apply("<>4", "ab".into()).unwrap(), "<<ab");
+    }
+
+    #[test]
+    fn zero_padding_goes_after_sign() {
+        assert_eq!(apply("05", Value::Integer(-42)).unwrap(), "-0042");
+        assert_eq!(apply("06.2", Value::Float(1.256)).unwrap(), "001.26");
+    }
+
+    #[test]
+    fn zero_padding_only_applies_to_numbers() {
+        assert_eq!(apply("04", "ab".into()).unwrap(), "ab  ");
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(apply(".2", Value::Float(2.0 / 3.0)).unwrap(), "0.67");
+        assert_eq!(apply(".1", Value::Integer(2)).unwrap(), "2.0");
+        assert_eq!(apply(".3", "abcdef".into()).unwrap(), "abc");
+        assert_eq!(apply(">8.3", Value::Float(1.0)).unwrap(), "   1.000");
+    }
+
+    #[test]
+    fn text_longer_than_width_is_not_cut() {
+        assert_eq!(apply("2", "abcd".into()).unwrap(), "abcd");
+    }
+
+    #[test]
+    fn invalid_specs() {
+        for spec in ["x", "5x", ".", ".x", "<<<"] {
+            assert!(FormatSpec::parse(spec).is_none(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn escaped_braces() {
+        let one = [Value::Integer(1)];
+
+        assert_eq!(format_values("{{{}}}", &one).unwrap(), "{1}");
+        assert_eq!(format_values("}}{{", &[]).unwrap(), "}{");
+        assert_eq!(format_values("{{:>3}}", &[]).unwrap(), "{:>3}");
+        assert!(format_values("}", &[]).is_err());
+        assert!(format_values("{", &[]).is_err());
+    }
 }
@@ -0,0 +1,26 @@
+/// Import paths starting with this prefix refer to the standard library,
+/// which is embedded in the binary.
+pub(crate) const PREFIX: &str = "std/";
+
+/// The module which imports the whole standard library.
+pub(crate) const PRELUDE: &str = "std/prelude";
+
+const MODULES: &[(&str, &str)] = &[
+    // The standard library used to be imported as `stdlib`, which re-exports
+    // the prelude with the old names.
+    ("stdlib", include_str!("../../std/stdlib.tapr")),
+    ("std/collections", include_str!("../../std/collections.tapr")),
+    ("std/functional", include_str!("../../std/functional.tapr")),
+    ("std/math", include_str!("../../std/math.tapr")),
+    ("std/prelude", include_str!("../../std/prelude.tapr")),
+    ("std/strings", include_str!("../../std/strings.tapr")),
+];
+
+/// Returns the source of the standard library module `name`.
+pub(crate) fn source(name: &str) -> Option<&'static str> {
+    MODULES.iter().find(|(n, _)| *n == name).map(|(_, source)| *source)
+}
+
+pub(crate) fn module_names() -> impl Iterator<Item = &'static str> {
+    MODULES.iter().map(|(name, _)| *name)
+}
@@ -0,0 +1,120 @@
+use super::{stdlib, Environment, Interpreter, Value};
+
+impl Interpreter<'_> {
+    /// Suggests a defined symbol for the undefined symbol `name`, including
+    /// symbols in modules, e.g. `string/join` for `join`.
+    pub(crate) fn suggest_symbol(&self, name: &str) -> Option<String> {
+        let mut candidates = Vec::new();
+
+        for candidate in self.environment.names() {
+            if let Some(Value::Module(module)) =
+                self.environment.get(&candidate)
+            {
+                candidates.extend(qualified_names(&candidate, &module));
+            }
+
+            candidates.push(candidate);
+        }
+
+        suggest(name, candidates)
+    }
+
+    /// Suggests a defined module for the undefined module `name`.
+    pub(crate) fn suggest_module(&self, name: &str) -> Option<String> {
+        let modules = self.environment.names().into_iter().filter(|n| {
+            matches!(self.environment.get(n), Some(Value::Module(_)))
+        });
+
+        suggest(name, modules)
+    }
+
+    /// Suggests a symbol in `module` for the undefined symbol `name`.
+    pub(crate) fn suggest_in_module(
+        module_name: &str,
+        module: &Environment,
+        name: &str,
+    ) -> Option<String> {
+        suggest(name, qualified_names(module_name, module))
+    }
+
+    /// Suggests a module of the standard library for the unknown module
+    /// `name`, which starts with the `std/` prefix.
+    pub(crate) fn suggest_std_module(name: &str) -> Option<String> {
+        let name = name.strip_prefix(stdlib::PREFIX)?;
+
+        let modules = stdlib::module_names()
+            .filter_map(|n| n.strip_prefix(stdlib::PREFIX))
+            .map(str::to_owned);
+
+        closest(name, modules)
+            .map(|module| format!("did you mean `{}{module}`?", stdlib::PREFIX))
+    }
+}
+
+fn qualified_names(module_name: &str, module: &Environment) -> Vec<String> {
+    module
+        .exported_names()
+        .iter()
+        .map(|n| format!("{module_name}/{n}"))
+        .collect()
+}
+
+/// Returns a "did you mean" hint with the candidate closest to `name`, if any
+/// is close enough.
+fn suggest(
+    name: &str,
+    candidates: impl IntoIterator<Item = String>,
+) -> Option<String> {
+    closest(name, candidates)
+        .map(|candidate| format!("did you mean `{candidate}`?"))
+}
+
+/// Returns the candidate closest to `name`, if any is close enough.
+/// Candidates in modules also match on their name without the module, but
+/// unqualified candidates are preferred.
+fn closest(
+    name: &str,
+    candidates: impl IntoIterator<Item = String>,
+) -> Option<String> {
+    let max_distance = ((name.chars().count() + 2) / 3).max(1);
+
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let (is_qualified, distance) = match candidate.split_once('/') {
+                Some((_, unqualified)) if unqualified != name => {
+                    (true, distance(name, unqualified))
+                },
+                Some(_) => (true, 0),
+                None => (false, distance(name, &candidate)),
+            };
+
+            (candidate != name && distance <= max_distance)
+                .then_some(((distance, is_qualified), candidate))
+        })
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Returns the Levenshtein distance between `left` and `right`.
+fn distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+
+    for (i, l) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, r) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(l != *r);
+
+            current.push(
+                substitution.min(previous[j + 1] + 1).min(current[j] + 1),
+            );
+        }
+
+        previous = current;
+    }
+
+    previous[right.len()]
+}
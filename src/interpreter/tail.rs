@@ -0,0 +1,311 @@
+use std::sync::Arc;
+
+use super::value::Callable;
+use super::{Arguments, CallableType, Interpreter, Value};
+use crate::error::{Error, ErrorKind, Frame};
+use crate::location::Location;
+use crate::parser::ast::{Node, NodeData, Special};
+use crate::parser::parameters::ParameterType;
+use crate::Result;
+
+/// The maximum amount of nested, non-tail calls before evaluation is aborted.
+pub const MAX_CALL_DEPTH: usize = 10_000;
+
+// If less than `STACK_RED_ZONE` bytes of stack remain, a new segment of
+// `STACK_SEGMENT_SIZE` bytes is allocated before calling a function.
+const STACK_RED_ZONE: usize = 128 * 1024;
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+/// The result of evaluating a node in tail position.
+///
+/// Instead of calling a function in tail position, the call is returned to
+/// the caller, which runs it after the current function has returned. This
+/// keeps the Rust stack from growing on tail calls.
+pub enum Tail {
+    Value(Value),
+    Call {
+        callable: Arc<dyn Callable>,
+        arguments: Vec<Value>,
+        location: Location,
+    },
+    Recur {
+        arguments: Vec<Value>,
+        location: Location,
+    },
+}
+
+impl Interpreter<'_> {
+    /// Calls `callable` with `arguments`, running any tail calls it returns.
+    pub(crate) fn call_at(
+        &mut self,
+        callable: Arc<dyn Callable>,
+        arguments: Vec<Value>,
+        location: Location,
+    ) -> Result<Value> {
+        if self.call_stack.len() >= MAX_CALL_DEPTH {
+            return Err(self.with_backtrace(Error::new(
+                location,
+                ErrorKind::MaxCallDepth(MAX_CALL_DEPTH),
+            )));
+        }
+
+        self.call_stack.push(Frame::new(&*callable, location.clone()));
+
+        let result =
+            stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || {
+                self.trampoline(callable, arguments, location)
+            });
+
+        self.call_stack.pop();
+
+        result
+    }
+
+    /// Calls `value` with `arguments`, like `(value arguments...)` would.
+    pub fn call(
+        &mut self,
+        value: &Value,
+        arguments: Vec<Value>,
+    ) -> Result<Value> {
+        match value {
+            Value::Callable(callable) => {
+                if matches!(callable.callable_type(), CallableType::Macro) {
+                    return Err(
+                        ErrorKind::MacroNotExpanded(value.clone()).into()
+                    );
+                }
+
+                self.call_at(callable.clone(), arguments, Location::host())
+            },
+            Value::Map(_) | Value::Keyword(_) => lookup(value, &arguments),
+            value => Err(ErrorKind::NotCallable(value.clone()).into()),
+        }
+    }
+
+    /// Calls the global function `name` with `arguments`.
+    pub fn call_by_name(
+        &mut self,
+        name: &str,
+        arguments: Vec<Value>,
+    ) -> Result<Value> {
+        let value = self.get_global(name)?;
+
+        self.call(&value, arguments)
+    }
+
+    /// Runs a `Tail` to completion.
+    pub fn resolve_tail(&mut self, tail: Tail) -> Result<Value> {
+        match tail {
+            Tail::Value(value) => Ok(value),
+            Tail::Call { callable, arguments, location } => {
+                self.call_at(callable, arguments, location)
+            },
+            Tail::Recur { location, .. } => {
+                Err(Error::new(location, ErrorKind::RecurNotInTailPosition))
+            },
+        }
+    }
+
+    /// Evaluates `node`, returning calls in tail position instead of running
+    /// them.
+    pub(crate) fn eval_tail(&mut self, node: &Node) -> Result<Tail> {
+        match node.data() {
+            NodeData::Special(special) => {
+                match &**special {
+                    Special::If { condition, then, else_branch } => {
+                        if condition.accept(self)?.is_truthy() {
+                            self.eval_tail(then)
+                        } else if let Some(else_branch) = else_branch {
+                            self.eval_tail(else_branch)
+                        } else {
+                            Ok(Tail::Value(Value::Nil))
+                        }
+                    },
+                    Special::And { values } => {
+                        self.eval_short_circuit(
+                            values,
+                            true.into(),
+                            Value::is_falsy,
+                        )
+                    },
+                    Special::Or { values } => {
+                        self.eval_short_circuit(
+                            values,
+                            Value::Nil,
+                            Value::is_truthy,
+                        )
+                    },
+                    Special::NilCoalesce { values } => {
+                        self.eval_short_circuit(values, Value::Nil, |v| {
+                            !matches!(v, Value::Nil)
+                        })
+                    },
+                    Special::Do { body } => self.eval_body_tail(body),
+                    Special::Cond { clauses } => self.eval_cond(clauses),
+                    Special::Match { value, clauses } => {
+                        self.eval_match(value, clauses)
+                    },
+                    Special::Let { bindings, body } => {
+                        self.eval_let(bindings, body, &node.location())
+                    },
+                    Special::Loop { bindings, body } => {
+                        self.eval_loop(bindings, body, &node.location())
+                    },
+                    Special::Recur { arguments } => {
+                        let arguments = arguments
+                            .iter()
+                            .map(|n| n.accept(self))
+                            .collect::<Result<Vec<_>>>()?;
+
+                        Ok(Tail::Recur { arguments, location: node.location() })
+                    },
+                    _ => node.accept(self).map(Tail::Value),
+                }
+            },
+            NodeData::List { literal: false, nodes } if !nodes.is_empty() => {
+                self.eval_call(nodes)
+            },
+            _ => node.accept(self).map(Tail::Value),
+        }
+    }
+
+    /// Evaluates `body`, with its last node in tail position.
+    pub(crate) fn eval_body_tail(&mut self, body: &[Node]) -> Result<Tail> {
+        let Some((last, rest)) = body.split_last() else {
+            return Ok(Tail::Value(Value::Nil));
+        };
+
+        for node in rest {
+            node.accept(self)?;
+        }
+
+        self.eval_tail(last)
+    }
+
+    /// Evaluates the callable and arguments of a call, returning the call
+    /// instead of running it. Maps and keywords are called by looking up a
+    /// key, which is done immediately.
+    pub(crate) fn eval_call(&mut self, nodes: &[Node]) -> Result<Tail> {
+        let node = &nodes[0];
+        let value = node.accept(self)?;
+
+        match value {
+            Value::Callable(callable) => {
+                if matches!(callable.callable_type(), CallableType::Macro) {
+                    return Err(Error::new(
+                        node.location(),
+                        ErrorKind::MacroNotExpanded(Value::Callable(callable)),
+                    ));
+                }
+
+                let arguments = self.eval_arguments(&nodes[1..])?;
+
+                Ok(Tail::Call {
+                    callable,
+                    arguments,
+                    location: node.location(),
+                })
+            },
+            Value::Map(_) | Value::Keyword(_) => {
+                let arguments = self.eval_arguments(&nodes[1..])?;
+
+                lookup(&value, &arguments).map(Tail::Value).map_err(|e| {
+                    Self::add_location_to_error(e, &node.location())
+                })
+            },
+            value => {
+                Err(Error::new(node.location(), ErrorKind::NotCallable(value)))
+            },
+        }
+    }
+
+    /// Attaches the current call stack to `error`, unless an inner call
+    /// already did.
+    fn with_backtrace(&self, mut error: Error) -> Error {
+        if error.backtrace.is_empty() {
+            error.backtrace = self.call_stack.iter().rev().cloned().collect();
+        }
+
+        error
+    }
+
+    fn eval_arguments(&mut self, nodes: &[Node]) -> Result<Vec<Value>> {
+        nodes.iter().map(|n| n.accept(self)).collect()
+    }
+
+    fn trampoline(
+        &mut self,
+        mut callable: Arc<dyn Callable>,
+        mut arguments: Vec<Value>,
+        mut location: Location,
+    ) -> Result<Value> {
+        loop {
+            let parameters = callable.parameters();
+
+            let tail = Arguments::new(&parameters, arguments)
+                .and_then(|arguments| callable.call_tail(self, arguments))
+                .map_err(|e| {
+                    self.with_backtrace(Self::add_location_to_error(
+                        e, &location,
+                    ))
+                })?;
+
+            match tail {
+                Tail::Call {
+                    callable: next_callable,
+                    arguments: next_arguments,
+                    location: next_location,
+                } => {
+                    // A tail call replaces the frame of the current call.
+                    *self
+                        .call_stack
+                        .last_mut()
+                        .expect("Trampoline should have a frame.") =
+                        Frame::new(&*next_callable, next_location.clone());
+
+                    callable = next_callable;
+                    arguments = next_arguments;
+                    location = next_location;
+                },
+                tail => return self.resolve_tail(tail),
+            }
+        }
+    }
+}
+
+/// Looks up a key in a map, for `(map key)` and `(:keyword map)`, with an
+/// optional default value.
+fn lookup(value: &Value, arguments: &[Value]) -> Result<Value> {
+    let (key_or_map, default) = match arguments.len() {
+        1 => (&arguments[0], Value::Nil),
+        2 => (&arguments[0], arguments[1].clone()),
+        0 => {
+            return Err(ErrorKind::WrongAmountOfMinArgs {
+                expected: 1,
+                actual: 0,
+            }
+            .into())
+        },
+        actual => {
+            return Err(
+                ErrorKind::WrongAmountOfMaxArgs { expected: 2, actual }.into()
+            )
+        },
+    };
+
+    let found = match (value, key_or_map) {
+        (Value::Map(map), key) | (key @ Value::Keyword(_), Value::Map(map)) => {
+            map.get(key).cloned()
+        },
+        (Value::Keyword(_), Value::Nil) => None,
+        (_, other) => {
+            return Err(ErrorKind::InvalidArgument {
+                expected: vec![ParameterType::Map],
+                actual: other.clone(),
+            }
+            .into())
+        },
+    };
+
+    Ok(found.unwrap_or(default))
+}
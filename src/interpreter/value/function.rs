@@ -1,19 +1,33 @@
 use super::{Callable, CallableType, Value};
 use crate::interpreter::environment::Environment;
-use crate::interpreter::{Arguments, Interpreter};
+use crate::interpreter::{Arguments, Interpreter, Tail};
 use crate::parser::ast::Node;
 use crate::parser::parameters::Parameters;
 use crate::Result;
 
 #[derive(Debug, Clone)]
 pub struct Function {
+    pub name: Option<String>,
     pub parameters: Parameters,
     pub body: Vec<Node>,
+    /// The environment the function was defined in. See [`Environment`] for
+    /// why this leaks functions which are stored in it.
+    pub environment: Environment,
 }
 
 impl Function {
-    pub fn new(parameters: Parameters, body: Vec<Node>) -> Self {
-        Self { parameters, body }
+    pub fn new(
+        parameters: Parameters,
+        body: Vec<Node>,
+        environment: Environment,
+    ) -> Self {
+        Self { name: None, parameters, body, environment }
+    }
+
+    #[must_use]
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
     }
 }
 
@@ -29,26 +43,49 @@ impl Callable for Function {
         intp: &mut Interpreter,
         arguments: Arguments,
     ) -> Result<Value> {
-        let mut function_environment = Environment::new();
-        arguments.add_to_env(&mut function_environment)?;
+        let tail = self.call_tail(intp, arguments)?;
 
-        intp.push_environment(function_environment);
+        intp.resolve_tail(tail)
+    }
 
-        let mut values = self
-            .body
-            .iter()
-            .map(|n| n.accept(intp))
-            .collect::<Result<Vec<_>>>()?;
+    fn call_tail(
+        &self,
+        intp: &mut Interpreter,
+        arguments: Arguments,
+    ) -> Result<Tail> {
+        let mut arguments = arguments;
 
-        intp.exit_scope();
+        // A `recur` in tail position calls the function again with new
+        // arguments.
+        loop {
+            let function_environment =
+                Environment::with_parent(self.environment.clone());
+            arguments.add_to_env(&function_environment)?;
 
-        Ok(values.pop().unwrap_or(Value::Nil))
+            let tail = intp.with_environment(function_environment, |intp| {
+                intp.eval_body_tail(&self.body)
+            })?;
+
+            match tail {
+                Tail::Recur { arguments: values, location } => {
+                    arguments = Arguments::new(&self.parameters, values)
+                        .map_err(|e| {
+                            Interpreter::add_location_to_error(e, &location)
+                        })?;
+                },
+                tail => return Ok(tail),
+            }
+        }
     }
 
     fn arity(&self) -> usize {
         self.parameters.len()
     }
 
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     fn callable_type(&self) -> CallableType {
         CallableType::Function
     }
@@ -57,3 +94,56 @@ impl Callable for Function {
         self.parameters.clone()
     }
 }
+
+/// A function that is called during macro expansion, with its arguments as
+/// unevaluated code. The value it returns is evaluated in place of the call.
+#[derive(Debug, Clone)]
+pub struct Macro {
+    function: Function,
+}
+
+impl Macro {
+    pub fn new(function: Function) -> Self {
+        Self { function }
+    }
+}
+
+impl std::fmt::Display for Macro {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<macro ({})>", self.function.parameters.len())
+    }
+}
+
+impl Callable for Macro {
+    fn call(
+        &self,
+        intp: &mut Interpreter,
+        arguments: Arguments,
+    ) -> Result<Value> {
+        self.function.call(intp, arguments)
+    }
+
+    fn call_tail(
+        &self,
+        intp: &mut Interpreter,
+        arguments: Arguments,
+    ) -> Result<Tail> {
+        self.function.call_tail(intp, arguments)
+    }
+
+    fn arity(&self) -> usize {
+        self.function.arity()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.function.name()
+    }
+
+    fn callable_type(&self) -> CallableType {
+        CallableType::Macro
+    }
+
+    fn parameters(&self) -> Parameters {
+        self.function.parameters()
+    }
+}
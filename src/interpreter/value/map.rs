@@ -0,0 +1,189 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use super::Value;
+
+/// A map from values to values, which keeps its keys in insertion order.
+///
+/// Unlike `==`, keys are compared strictly, so `1` and `1.0` are different
+/// keys and a float key is only found with a float of the same bits.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    entries: IndexMap<Key, Value>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.entries.get(&Key(key.clone()))
+    }
+
+    pub fn contains_key(&self, key: &Value) -> bool {
+        self.entries.contains_key(&Key(key.clone()))
+    }
+
+    /// Inserts `value` at `key`, returning the old value if `key` was already
+    /// present. An existing key keeps its position.
+    pub fn insert(&mut self, key: Value, value: Value) -> Option<Value> {
+        self.entries.insert(Key(key), value)
+    }
+
+    pub fn remove(&mut self, key: &Value) -> Option<Value> {
+        self.entries.shift_remove(&Key(key.clone()))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Value> {
+        self.entries.keys().map(|key| &key.0)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.entries.values()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.entries.iter().map(|(key, value)| (&key.0, value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl FromIterator<(Value, Value)> for Map {
+    fn from_iter<T: IntoIterator<Item = (Value, Value)>>(iter: T) -> Self {
+        let entries =
+            iter.into_iter().map(|(key, value)| (Key(key), value)).collect();
+
+        Self { entries }
+    }
+}
+
+impl IntoIterator for Map {
+    type IntoIter = std::vec::IntoIter<(Value, Value)>;
+    type Item = (Value, Value);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries
+            .into_iter()
+            .map(|(key, value)| (key.0, value))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries =
+            self.iter().map(|(k, v)| format!("{k} {v}")).collect::<Vec<_>>();
+
+        write!(f, "{{{}}}", entries.join(" "))
+    }
+}
+
+/// A value used as a key, which is only equal to values of the same type and
+/// contents.
+#[derive(Debug, Clone)]
+struct Key(Value);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        strict_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Key {
+}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        strict_hash(&self.0, state);
+    }
+}
+
+fn strict_eq(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Float(left), Value::Float(right)) => {
+            left.to_bits() == right.to_bits()
+        },
+        (Value::List(left), Value::List(right)) => {
+            left.len() == right.len()
+                && left.iter().zip(right).all(|(l, r)| strict_eq(l, r))
+        },
+        (Value::Map(left), Value::Map(right)) => {
+            left.len() == right.len()
+                && left.entries.iter().all(|(key, value)| {
+                    right
+                        .entries
+                        .get(key)
+                        .is_some_and(|other| strict_eq(value, other))
+                })
+        },
+        (Value::Callable(left), Value::Callable(right)) => {
+            Arc::ptr_eq(left, right)
+        },
+        (Value::Module(left), Value::Module(right)) => left.ptr_eq(right),
+        (Value::Integer(_), Value::Float(_))
+        | (Value::Float(_), Value::Integer(_)) => false,
+        (left, right) => left == right,
+    }
+}
+
+fn strict_hash<H: Hasher>(value: &Value, state: &mut H) {
+    std::mem::discriminant(value).hash(state);
+
+    match value {
+        Value::Integer(integer) => integer.hash(state),
+        Value::Float(float) => float.to_bits().hash(state),
+        Value::List(items) => {
+            items.len().hash(state);
+
+            for item in items {
+                strict_hash(item, state);
+            }
+        },
+        Value::Map(map) => {
+            // Equal maps may have a different order, so the hashes of the
+            // entries are combined in an order-independent way.
+            let hash = map
+                .entries
+                .iter()
+                .map(|(key, value)| {
+                    let mut hasher = DefaultHasher::new();
+                    key.hash(&mut hasher);
+                    strict_hash(value, &mut hasher);
+                    hasher.finish()
+                })
+                .fold(0, u64::wrapping_add);
+
+            map.len().hash(state);
+            hash.hash(state);
+        },
+        Value::Boolean(boolean) => boolean.hash(state),
+        Value::String(string)
+        | Value::Symbol(string)
+        | Value::Keyword(string) => string.hash(state),
+        Value::Callable(callable) => {
+            Arc::as_ptr(callable).cast::<()>().hash(state);
+        },
+        // Modules are compared by identity, which their handle doesn't
+        // expose, so they all share one hash.
+        Value::Nil | Value::Module(_) => (),
+    }
+}
@@ -1,24 +1,29 @@
 mod function;
+mod map;
 
 use std::cmp::Ordering;
 use std::sync::Arc;
 
-pub use function::Function;
+pub use function::{Function, Macro};
+pub use map::Map;
 
 use super::environment::Environment;
 use super::native::NativeFunction;
-use super::Interpreter;
+use super::{Interpreter, Tail};
+use crate::parser::string::escape;
 use crate::{Arguments, Parameters, Result};
 
 #[derive(Debug, Clone)]
 pub enum Value {
     Nil,
     Boolean(bool),
-    Number(f64),
+    Integer(i64),
+    Float(f64),
     String(String),
     Symbol(String),
     Keyword(String),
     List(Vec<Self>),
+    Map(Map),
     Callable(Arc<dyn Callable>),
     Module(Environment),
 }
@@ -36,9 +41,19 @@ impl Value {
         !self.is_truthy()
     }
 
+    /// Returns the value of an integer or float as a float.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Integer(integer) => Some(*integer as f64),
+            Value::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+
     pub fn repl_repr(&self) -> String {
         match self {
-            Value::String(string) => format!("\"{string}\""),
+            Value::String(string) => format!("\"{}\"", escape(string)),
             other => other.to_string(),
         }
     }
@@ -50,9 +65,15 @@ impl From<bool> for Value {
     }
 }
 
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
+
 impl From<f64> for Value {
     fn from(value: f64) -> Self {
-        Value::Number(value)
+        Value::Float(value)
     }
 }
 
@@ -68,6 +89,12 @@ impl From<String> for Value {
     }
 }
 
+impl From<Map> for Value {
+    fn from(value: Map) -> Self {
+        Value::Map(value)
+    }
+}
+
 impl From<NativeFunction> for Value {
     fn from(value: NativeFunction) -> Self {
         Value::Callable(Arc::new(value))
@@ -86,12 +113,20 @@ impl PartialEq for Value {
         match (self, other) {
             (Value::Nil, Value::Nil) => true,
             (Value::Boolean(left), Value::Boolean(right)) => left == right,
-            (Value::Number(left), Value::Number(right)) => {
-                (*left - *right).abs() < f64::EPSILON
+            (Value::Integer(left), Value::Integer(right)) => left == right,
+            (
+                Value::Integer(_) | Value::Float(_),
+                Value::Integer(_) | Value::Float(_),
+            ) => {
+                self.as_float().zip(other.as_float()).is_some_and(
+                    |(left, right)| (left - right).abs() < f64::EPSILON,
+                )
             },
             (Value::String(left), Value::String(right)) => left == right,
             (Value::Symbol(left), Value::Symbol(right)) => left == right,
+            (Value::Keyword(left), Value::Keyword(right)) => left == right,
             (Value::List(left), Value::List(right)) => left == right,
+            (Value::Map(left), Value::Map(right)) => left == right,
             _ => false,
         }
     }
@@ -105,9 +140,13 @@ impl PartialOrd for Value {
             (Value::Boolean(left), Value::Boolean(right)) => {
                 left.partial_cmp(right)
             },
-            (Value::Number(left), Value::Number(right)) => {
+            (Value::Integer(left), Value::Integer(right)) => {
                 left.partial_cmp(right)
             },
+            (
+                Value::Integer(_) | Value::Float(_),
+                Value::Integer(_) | Value::Float(_),
+            ) => self.as_float()?.partial_cmp(&other.as_float()?),
             (Value::String(left), Value::String(right)) => {
                 left.partial_cmp(right)
             },
@@ -124,7 +163,15 @@ impl std::fmt::Display for Value {
         match self {
             Value::Nil => write!(f, "nil"),
             Value::Boolean(boolean) => write!(f, "{boolean}"),
-            Value::Number(number) => write!(f, "{number}"),
+            Value::Integer(integer) => write!(f, "{integer}"),
+            // Print floats with a fraction, so they are distinct from
+            // integers.
+            Value::Float(float)
+                if float.is_finite() && float.fract() == 0.0 =>
+            {
+                write!(f, "{float:.1}")
+            },
+            Value::Float(float) => write!(f, "{float}"),
             Value::String(string) => write!(f, "{string}"),
             Value::Symbol(symbol) => write!(f, "{symbol}"),
             Value::Keyword(keyword) => write!(f, ":{keyword}"),
@@ -139,6 +186,7 @@ impl std::fmt::Display for Value {
                         .join(" ")
                 )
             },
+            Value::Map(map) => map.fmt(f),
             Value::Callable(callable) => callable.fmt(f),
             Value::Module(environment) => {
                 write!(f, "<module ({})>", environment.len())
@@ -150,6 +198,7 @@ impl std::fmt::Display for Value {
 pub enum CallableType {
     Native,
     Function,
+    Macro,
 }
 
 impl std::fmt::Display for CallableType {
@@ -157,6 +206,7 @@ impl std::fmt::Display for CallableType {
         write!(f, "{}", match self {
             CallableType::Native => "native fn",
             CallableType::Function => "fn",
+            CallableType::Macro => "macro",
         })
     }
 }
@@ -168,8 +218,23 @@ pub trait Callable: Send + Sync {
         arguments: Arguments,
     ) -> Result<Value>;
 
+    /// Calls the callable, but may return a call in tail position instead of
+    /// running it.
+    fn call_tail(
+        &self,
+        intp: &mut Interpreter,
+        arguments: Arguments,
+    ) -> Result<Tail> {
+        self.call(intp, arguments).map(Tail::Value)
+    }
+
     fn arity(&self) -> usize;
 
+    /// The name of the callable, which is shown in backtraces.
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
     fn callable_type(&self) -> CallableType;
 
     fn parameters(&self) -> Parameters;
@@ -6,6 +6,11 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::module_inception)]
 #![allow(clippy::wildcard_imports)]
+// `Error` carries its location with it, which makes it too large for this
+// lint. Boxing every error isn't worth it for an interpreter.
+#![allow(clippy::result_large_err)]
+// Names like `Parameter::parameter_types` are clearer than the alternatives.
+#![allow(clippy::struct_field_names)]
 
 extern crate alloc;
 extern crate pest;
@@ -26,8 +31,9 @@ pub type Result<T> = std::result::Result<T, error::Error>;
 pub use cli::main;
 pub use error::{Error as TaprError, ErrorKind as TaprErrorKind};
 pub use interpreter::{
-    Arguments, Callable, CallableType, Environment, Interpreter,
-    NativeFunction, NativeFunctionImpl, Value,
+    Arguments, Callable, CallableType, Environment, FromValue, Interpreter,
+    IntoNativeFunction, IntoValue, NativeFunction, NativeFunctionImpl,
+    NativeModule, NativeModules, Value,
 };
 pub use parser::ast::{Node, NodeData};
 pub use parser::parameters::{Parameter, ParameterType, Parameters};
@@ -1,39 +1,131 @@
+use std::sync::Arc;
+
+use pest::error::{InputLocation, LineColLocation};
 use pest::iterators::Pair;
 
 use crate::parser::Rule;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// The name and text of a parsed source, shared by all locations in it.
+#[derive(Debug)]
+pub struct Source {
+    name: String,
+    text: String,
+}
+
+impl Source {
+    pub fn new(name: &str, text: &str) -> Self {
+        Self { name: name.to_owned(), text: text.to_owned() }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-// TODO? Add file location to Location?
-pub struct Location(usize, usize);
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// A position in a source, with the span of bytes it covers.
+#[derive(Debug, Clone)]
+pub struct Location {
+    source: Option<Arc<Source>>,
+    line_no: usize,
+    col_no: usize,
+    span: (usize, usize),
+}
 
 impl Location {
     pub fn new(line_no: usize, col_no: usize) -> Self {
-        Self(line_no, col_no)
+        Self { source: None, line_no, col_no, span: (0, 0) }
+    }
+
+    /// The location of calls made from Rust, which aren't in any source.
+    pub fn host() -> Self {
+        let source = Arc::new(Source::new("<host>", ""));
+
+        Self { source: Some(source), line_no: 0, col_no: 0, span: (0, 0) }
     }
 
     pub fn from_pair(pair: &Pair<Rule>) -> Self {
         pair.into()
     }
 
+    pub fn from_pest_error(
+        error: &pest::error::Error<Rule>,
+        source: &Arc<Source>,
+    ) -> Self {
+        let (LineColLocation::Pos((line_no, col_no))
+        | LineColLocation::Span((line_no, col_no), _)) = error.line_col;
+
+        let span = match error.location {
+            InputLocation::Pos(position) => (position, position),
+            InputLocation::Span(span) => span,
+        };
+
+        Self { source: Some(source.clone()), line_no, col_no, span }
+    }
+
     pub fn line_no(&self) -> usize {
-        self.0
+        self.line_no
     }
 
     pub fn col_no(&self) -> usize {
-        self.1
+        self.col_no
+    }
+
+    pub fn source(&self) -> Option<&Arc<Source>> {
+        self.source.as_ref()
+    }
+
+    pub(crate) fn set_source(&mut self, source: &Arc<Source>) {
+        self.source = Some(source.clone());
+    }
+
+    /// Renders the line this location is on, with the span underlined.
+    pub fn snippet(&self) -> Option<String> {
+        let source = self.source.as_ref()?;
+        let line = source.text().lines().nth(self.line_no.checked_sub(1)?)?;
+
+        let (start, end) = self.span;
+        let span_text = source.text().get(start..end).unwrap_or_default();
+        // Only the part of the span on the first line is underlined.
+        let first_line = span_text.lines().next().unwrap_or_default();
+        let width = first_line.chars().count().max(1);
+
+        let gutter = " ".repeat(self.line_no.to_string().len());
+        let indent = " ".repeat(self.col_no.saturating_sub(1));
+
+        Some(format!(
+            "{gutter} |\n{} | {line}\n{gutter} | {indent}{}",
+            self.line_no,
+            "^".repeat(width)
+        ))
     }
 }
 
 impl std::fmt::Display for Location {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{:03}:{:03}]", self.line_no(), self.col_no())
+        if let Some(source) = &self.source {
+            write!(f, "{}", source.name())?;
+
+            // Locations outside of any source have no position.
+            if self.line_no == 0 {
+                return Ok(());
+            }
+
+            write!(f, ":")?;
+        }
+
+        write!(f, "{}:{}", self.line_no, self.col_no)
     }
 }
 
 impl From<&Pair<'_, Rule>> for Location {
     fn from(pair: &Pair<Rule>) -> Self {
         let (line_no, col_no) = pair.line_col();
-        Location::new(line_no, col_no)
+        let span = pair.as_span();
+
+        Self { source: None, line_no, col_no, span: (span.start(), span.end()) }
     }
 }
@@ -1,11 +1,16 @@
+use std::sync::Arc;
+
 use pest::iterators::{Pair, Pairs};
 use pest::Parser as PestParser;
 
 use super::parameters::ParameterType;
 use crate::env::{DebugAst, DEBUG_AST, DEBUG_PARSER};
+use crate::error::{Error, ErrorKind};
 use crate::graph::GraphVisitor;
-use crate::location::Location;
+use crate::location::{Location, Source};
 use crate::parser::parameters::{Parameter, Parameters};
+use crate::parser::pattern::Pattern;
+use crate::parser::string::unescape;
 use crate::parser::{Parser, Rule};
 use crate::visitor::Visitor;
 use crate::Result;
@@ -20,10 +25,20 @@ pub struct Node {
 pub enum NodeData {
     Main(Vec<Node>),
     Special(Box<Special>),
-    List { literal: bool, nodes: Vec<Node> },
-    Symbol { module: Option<String>, value: String },
+    List {
+        literal: bool,
+        nodes: Vec<Node>,
+    },
+    Map(Vec<(Node, Node)>),
+    /// The text and values of an f-string, which are concatenated.
+    FString(Vec<Node>),
+    Symbol {
+        module: Option<String>,
+        value: String,
+    },
     Keyword(String),
-    Number(f64),
+    Integer(i64),
+    Float(f64),
     String(String),
     True,
     False,
@@ -35,6 +50,10 @@ impl Node {
         Self { location: Location::new(0, 0), data }
     }
 
+    pub(crate) fn new(data: NodeData, location: Location) -> Self {
+        Self { location, data }
+    }
+
     pub fn accept<T: std::fmt::Debug>(
         &self,
         visitor: &mut dyn Visitor<T>,
@@ -51,19 +70,11 @@ impl Node {
     }
 
     pub fn location(&self) -> Location {
-        self.location
+        self.location.clone()
     }
 
     pub fn from_string(source: &str, name: &str) -> Result<Node> {
-        let mut pairs = Parser::parse(Rule::main, source)?;
-
-        if *DEBUG_PARSER {
-            println!("{pairs:#?}");
-        }
-
-        let node = Node::parse_value(
-            pairs.next().expect("Pairs<Rule::main> panicked on next()"),
-        );
+        let node = Node::parse(&Arc::new(Source::new(name, source)))?;
 
         if !matches!(*DEBUG_AST, DebugAst::Off) {
             GraphVisitor::create_ast_graph(
@@ -76,11 +87,74 @@ impl Node {
         Ok(node)
     }
 
-    fn parse_value(pair: Pair<Rule>) -> Node {
+    fn parse(source: &Arc<Source>) -> Result<Node> {
+        let to_error = |error: pest::error::Error<Rule>| {
+            Error::new(
+                Location::from_pest_error(&error, source),
+                Box::new(error).into(),
+            )
+        };
+
+        let mut pairs =
+            Parser::parse(Rule::main, source.text()).map_err(to_error)?;
+
+        if *DEBUG_PARSER {
+            println!("{pairs:#?}");
+        }
+
+        Node::validate_strings(&pairs).map_err(to_error)?;
+        Node::validate_integers(&pairs, source)?;
+
+        let mut node = Node::parse_value(
+            pairs.next().expect("Pairs<Rule> panicked on next()"),
+        );
+
+        node.set_source(source);
+
+        Ok(node)
+    }
+
+    fn set_source(&mut self, source: &Arc<Source>) {
+        self.location.set_source(source);
+
+        for child in self.children_mut() {
+            child.set_source(source);
+        }
+    }
+
+    /// Returns mutable references to the direct children of this node,
+    /// including the data inside quoted values.
+    pub(crate) fn children_mut(&mut self) -> Vec<&mut Node> {
+        match &mut self.data {
+            NodeData::Main(nodes)
+            | NodeData::List { nodes, .. }
+            | NodeData::FString(nodes) => nodes.iter_mut().collect(),
+            NodeData::Map(entries) => {
+                entries.iter_mut().flat_map(|(k, v)| [k, v]).collect()
+            },
+            NodeData::Special(special) => special.children_mut(),
+            NodeData::Symbol { .. }
+            | NodeData::Keyword(_)
+            | NodeData::Integer(_)
+            | NodeData::Float(_)
+            | NodeData::String(_)
+            | NodeData::True
+            | NodeData::False
+            | NodeData::Nil => Vec::new(),
+        }
+    }
+
+    pub(crate) fn parse_value(pair: Pair<Rule>) -> Node {
         let location = Location::from_pair(&pair);
 
         let data = match pair.as_rule() {
             Rule::symbol => return Node::parse_symbol(pair),
+            Rule::auto_gensym => {
+                NodeData::Symbol {
+                    module: None,
+                    value: pair.as_str().to_owned(),
+                }
+            },
             Rule::keyword => {
                 NodeData::Keyword(
                     pair.into_inner()
@@ -101,33 +175,41 @@ impl Node {
             },
             Rule::string => {
                 NodeData::String(
-                    pair.into_inner()
-                        .next()
-                        .expect("Rule::string did not have inner text.")
-                        .as_str()
-                        .to_owned(),
+                    Node::parse_string(&pair)
+                        .expect("Strings should have been validated."),
                 )
             },
-            Rule::number => {
-                NodeData::Number(pair.as_str().parse().unwrap_or_else(|_| {
-                    panic!("unable to parse {} as number", pair.as_str())
-                }))
+            Rule::integer => {
+                NodeData::Integer(
+                    pair.as_str()
+                        .parse()
+                        .expect("Integers should have been validated."),
+                )
             },
+            Rule::float => NodeData::Float(Self::parse_float(&pair)),
             Rule::special => {
                 NodeData::Special(Box::new(Special::from_pair(pair)))
             },
-            Rule::plist => {
+            Rule::quote
+            | Rule::quasiquote
+            | Rule::unquote
+            | Rule::unquote_splicing => {
+                NodeData::Special(Box::new(Special::quoted(pair)))
+            },
+            Rule::plist | Rule::dplist => {
                 NodeData::List {
                     literal: false,
                     nodes: pair.into_inner().map(Node::parse_value).collect(),
                 }
             },
-            Rule::blist => {
+            Rule::blist | Rule::dblist => {
                 NodeData::List {
                     literal: true,
                     nodes: pair.into_inner().map(Node::parse_value).collect(),
                 }
             },
+            Rule::map | Rule::dmap => Node::parse_map(pair),
+            Rule::fstring => Node::parse_fstring(pair),
             Rule::main => {
                 NodeData::Main(
                     pair.into_inner()
@@ -148,6 +230,133 @@ impl Node {
         Node { location, data }
     }
 
+    fn parse_map(pair: Pair<Rule>) -> NodeData {
+        NodeData::Map(
+            pair.into_inner()
+                .map(|entry| {
+                    let mut inner = entry.into_inner();
+
+                    let key = Node::parse_value(
+                        inner.next().expect("Map entry did not have key."),
+                    );
+                    let value = Node::parse_value(
+                        inner.next().expect("Map entry did not have value."),
+                    );
+
+                    (key, value)
+                })
+                .collect(),
+        )
+    }
+
+    fn parse_string(pair: &Pair<Rule>) -> std::result::Result<String, String> {
+        let inner = pair
+            .clone()
+            .into_inner()
+            .next()
+            .expect("Rule::string did not have inner text.");
+
+        match inner.as_rule() {
+            Rule::inner => unescape(inner.as_str()),
+            // A newline right after the opening quotes is not part of the
+            // string, so multi-line strings can start on their own line.
+            Rule::long_inner => {
+                let text = inner.as_str();
+
+                unescape(text.strip_prefix('\n').unwrap_or(text))
+            },
+            Rule::raw_inner => Ok(inner.as_str().to_owned()),
+            rule => unreachable!("Encountered {rule:?} inside string."),
+        }
+    }
+
+    fn parse_fstring(pair: Pair<Rule>) -> NodeData {
+        let pieces = pair.into_inner().map(|piece| {
+            match piece.as_rule() {
+                Rule::fstring_text => {
+                    Node {
+                        location: Location::from_pair(&piece),
+                        data: NodeData::String(
+                            Node::parse_fstring_text(&piece)
+                                .expect("Strings should have been validated."),
+                        ),
+                    }
+                },
+                Rule::fstring_value => {
+                    Node::parse_value(
+                        piece
+                            .into_inner()
+                            .next()
+                            .expect("Rule::fstring_value did not have value."),
+                    )
+                },
+                rule => unreachable!("Encountered {rule:?} inside f-string."),
+            }
+        });
+
+        NodeData::FString(pieces.collect())
+    }
+
+    fn parse_fstring_text(
+        pair: &Pair<Rule>,
+    ) -> std::result::Result<String, String> {
+        unescape(&pair.as_str().replace("{{", "{").replace("}}", "}"))
+    }
+
+    /// Checks that all escape sequences in the strings in `pairs` are valid.
+    fn validate_strings(
+        pairs: &Pairs<Rule>,
+    ) -> std::result::Result<(), pest::error::Error<Rule>> {
+        for pair in pairs.clone().flatten() {
+            let result = match pair.as_rule() {
+                Rule::string => Node::parse_string(&pair),
+                Rule::fstring_text => Node::parse_fstring_text(&pair),
+                _ => continue,
+            };
+
+            if let Err(message) = result {
+                let error = pest::error::Error::new_from_span(
+                    pest::error::ErrorVariant::CustomError { message },
+                    pair.as_span(),
+                );
+
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all integer literals fit in an i64.
+    fn validate_integers(
+        pairs: &Pairs<Rule>,
+        source: &Arc<Source>,
+    ) -> Result<()> {
+        for pair in pairs.clone().flatten() {
+            if pair.as_rule() == Rule::integer
+                && pair.as_str().parse::<i64>().is_err()
+            {
+                let mut location = Location::from_pair(&pair);
+                location.set_source(source);
+
+                return Err(Error::new(
+                    location,
+                    ErrorKind::IntegerLiteralOutOfRange(
+                        pair.as_str().to_owned(),
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_float(pair: &Pair<Rule>) -> f64 {
+        pair.as_str().parse().unwrap_or_else(|_| {
+            panic!("unable to parse {} as number", pair.as_str())
+        })
+    }
+
     fn parse_symbol(pair: Pair<Rule>) -> Node {
         let location = Location::from_pair(&pair);
 
@@ -170,11 +379,97 @@ impl Node {
 
 #[derive(Debug, Clone)]
 pub enum Special {
-    Fn { parameters: Parameters, body: Vec<Node> },
-    If { condition: Node, then: Node, else_branch: Option<Node> },
-    Import { name: String, prefix: Option<String> },
-    Set { name: String, value: Node },
-    Var { name: String, value: Node },
+    Fn {
+        parameters: Parameters,
+        body: Vec<Node>,
+    },
+    Defmacro {
+        name: String,
+        parameters: Parameters,
+        body: Vec<Node>,
+    },
+    Do {
+        body: Vec<Node>,
+    },
+    If {
+        condition: Node,
+        then: Node,
+        else_branch: Option<Node>,
+    },
+    Import {
+        name: String,
+        prefix: Option<String>,
+        filter: Option<ImportFilter>,
+        rename: Vec<(String, String)>,
+    },
+    And {
+        values: Vec<Node>,
+    },
+    Or {
+        values: Vec<Node>,
+    },
+    NilCoalesce {
+        values: Vec<Node>,
+    },
+    Cond {
+        clauses: Vec<(Node, Node)>,
+    },
+    Match {
+        value: Node,
+        clauses: Vec<(Pattern, Node)>,
+    },
+    Let {
+        bindings: Vec<(Pattern, Node)>,
+        body: Vec<Node>,
+    },
+    Loop {
+        bindings: Vec<(Pattern, Node)>,
+        body: Vec<Node>,
+    },
+    Recur {
+        arguments: Vec<Node>,
+    },
+    While {
+        condition: Node,
+        body: Vec<Node>,
+    },
+    Dotimes {
+        name: String,
+        count: Node,
+        body: Vec<Node>,
+    },
+    Try {
+        body: Vec<Node>,
+        catch: Option<(String, Vec<Node>)>,
+        finally: Option<Vec<Node>>,
+    },
+    Set {
+        name: String,
+        value: Node,
+    },
+    Var {
+        name: String,
+        value: Node,
+    },
+    Quote {
+        datum: Node,
+    },
+    Quasiquote {
+        datum: Node,
+    },
+    Unquote {
+        value: Node,
+    },
+    UnquoteSplicing {
+        value: Node,
+    },
+}
+
+/// Selects the names an `import` binds in the importing scope.
+#[derive(Debug, Clone)]
+pub enum ImportFilter {
+    Only(Vec<String>),
+    Exclude(Vec<String>),
 }
 
 impl Special {
@@ -185,12 +480,28 @@ impl Special {
             .expect("Rule::special did not have inner pair.");
 
         match special.as_rule() {
+            Rule::and => Special::And { values: Self::body(special) },
+            Rule::cond => Special::cond(special),
+            Rule::defmacro => Special::defmacro(special),
             Rule::defn => Special::defn(special),
+            Rule::do_ => Special::Do { body: Self::body(special) },
+            Rule::dotimes => Special::dotimes(special),
             Rule::fn_ => Special::fn_(special),
             Rule::if_ => Special::if_(special),
             Rule::import => Special::import(special),
+            Rule::let_ => Special::let_(special),
+            Rule::loop_ => Special::loop_(special),
+            Rule::match_ => Special::match_(special),
+            Rule::nil_coalesce => {
+                Special::NilCoalesce { values: Self::body(special) }
+            },
+            Rule::or => Special::Or { values: Self::body(special) },
+            Rule::quote_form => Special::quoted(special),
+            Rule::recur => Special::recur(special),
             Rule::set => Special::set(special),
+            Rule::try_ => Special::try_(special),
             Rule::var => Special::var(special),
+            Rule::while_ => Special::while_(special),
             _ => {
                 unreachable!(
                     "Encountered '{}' inside Rule::special.",
@@ -200,6 +511,95 @@ impl Special {
         }
     }
 
+    fn quoted(pair: Pair<Rule>) -> Special {
+        let rule = pair.as_rule();
+
+        let node = Node::parse_value(
+            pair.into_inner().next().expect("Quoted value did not have value."),
+        );
+
+        match rule {
+            Rule::quote | Rule::quote_form => Special::Quote { datum: node },
+            Rule::quasiquote => Special::Quasiquote { datum: node },
+            Rule::unquote => Special::Unquote { value: node },
+            Rule::unquote_splicing => Special::UnquoteSplicing { value: node },
+            other => unreachable!("Encountered {other:?} as quoted value."),
+        }
+    }
+
+    fn defmacro(pair: Pair<Rule>) -> Special {
+        let mut inner = pair.into_inner();
+
+        let name = inner
+            .next()
+            .expect("Rule::defmacro did not have a name.")
+            .as_str()
+            .to_owned();
+
+        let Special::Fn { parameters, body } = Self::function(inner) else {
+            unreachable!("Special::function should return Special::Fn.")
+        };
+
+        Special::Defmacro { name, parameters, body }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Node> {
+        match self {
+            Special::Fn { body, .. }
+            | Special::Defmacro { body, .. }
+            | Special::Do { body } => body.iter_mut().collect(),
+            Special::If { condition, then, else_branch } => {
+                let mut children = vec![condition, then];
+                children.extend(else_branch);
+                children
+            },
+            Special::Import { .. } => Vec::new(),
+            Special::Try { body, catch, finally } => {
+                body.iter_mut()
+                    .chain(
+                        catch.iter_mut().flat_map(|(_, body)| body.iter_mut()),
+                    )
+                    .chain(finally.iter_mut().flatten())
+                    .collect()
+            },
+            Special::And { values }
+            | Special::Or { values }
+            | Special::NilCoalesce { values }
+            | Special::Recur { arguments: values } => {
+                values.iter_mut().collect()
+            },
+            Special::Cond { clauses } => {
+                clauses
+                    .iter_mut()
+                    .flat_map(|(test, result)| [test, result])
+                    .collect()
+            },
+            Special::Match { value, clauses } => {
+                std::iter::once(value)
+                    .chain(clauses.iter_mut().map(|(_, result)| result))
+                    .collect()
+            },
+            Special::Let { bindings, body }
+            | Special::Loop { bindings, body } => {
+                bindings
+                    .iter_mut()
+                    .map(|(_, value)| value)
+                    .chain(body.iter_mut())
+                    .collect()
+            },
+            Special::While { condition: value, body }
+            | Special::Dotimes { count: value, body, .. } => {
+                std::iter::once(value).chain(body.iter_mut()).collect()
+            },
+            Special::Set { value, .. }
+            | Special::Var { value, .. }
+            | Special::Quote { datum: value }
+            | Special::Quasiquote { datum: value }
+            | Special::Unquote { value }
+            | Special::UnquoteSplicing { value } => vec![value],
+        }
+    }
+
     fn defn(pair: Pair<Rule>) -> Special {
         let location = Location::from_pair(&pair);
 
@@ -239,6 +639,57 @@ impl Special {
         }
     }
 
+    fn cond(pair: Pair<Rule>) -> Special {
+        let clauses = pair
+            .into_inner()
+            .map(|clause| {
+                let mut inner = clause.into_inner();
+
+                let test = Node::parse_value(
+                    inner.next().expect("Rule::cond_clause did not have test."),
+                );
+                let result = Node::parse_value(
+                    inner
+                        .next()
+                        .expect("Rule::cond_clause did not have result."),
+                );
+
+                (test, result)
+            })
+            .collect();
+
+        Special::Cond { clauses }
+    }
+
+    fn match_(pair: Pair<Rule>) -> Special {
+        let mut inner = pair.into_inner();
+
+        let value = Node::parse_value(
+            inner.next().expect("Rule::match_ did not have value."),
+        );
+
+        let clauses = inner
+            .map(|clause| {
+                let mut inner = clause.into_inner();
+
+                let pattern = Pattern::from_pair(
+                    inner
+                        .next()
+                        .expect("Rule::match_clause did not have pattern."),
+                );
+                let result = Node::parse_value(
+                    inner
+                        .next()
+                        .expect("Rule::match_clause did not have result."),
+                );
+
+                (pattern, result)
+            })
+            .collect();
+
+        Special::Match { value, clauses }
+    }
+
     fn import(pair: Pair<Rule>) -> Special {
         let mut inner = pair.into_inner();
 
@@ -248,11 +699,123 @@ impl Special {
             .as_str()
             .to_owned();
 
-        let prefix = inner
-            .next()
-            .map(|p| p.into_inner().next().unwrap().as_str().to_owned());
+        let mut prefix = None;
+        let mut filter = None;
+        let mut rename = Vec::new();
+
+        for pair in inner {
+            match pair.as_rule() {
+                Rule::string => {
+                    prefix = Some(
+                        Node::parse_string(&pair)
+                            .expect("Strings should have been validated."),
+                    );
+                },
+                Rule::import_only => {
+                    filter = Some(ImportFilter::Only(Self::tokens(pair)));
+                },
+                Rule::import_exclude => {
+                    filter = Some(ImportFilter::Exclude(Self::tokens(pair)));
+                },
+                Rule::import_rename => {
+                    let tokens = Self::tokens(pair);
+
+                    rename = tokens
+                        .chunks_exact(2)
+                        .map(|pair| (pair[0].clone(), pair[1].clone()))
+                        .collect();
+                },
+                other => unreachable!("Encountered {other:?} inside import."),
+            }
+        }
 
-        Special::Import { name, prefix }
+        Special::Import { name, prefix, filter, rename }
+    }
+
+    fn tokens(pair: Pair<Rule>) -> Vec<String> {
+        pair.into_inner().map(|p| p.as_str().to_owned()).collect()
+    }
+
+    fn let_(pair: Pair<Rule>) -> Special {
+        let (bindings, body) = Self::bindings_and_body(pair);
+
+        Special::Let { bindings, body }
+    }
+
+    fn loop_(pair: Pair<Rule>) -> Special {
+        let (bindings, body) = Self::bindings_and_body(pair);
+
+        Special::Loop { bindings, body }
+    }
+
+    fn recur(pair: Pair<Rule>) -> Special {
+        Special::Recur {
+            arguments: pair.into_inner().map(Node::parse_value).collect(),
+        }
+    }
+
+    fn while_(pair: Pair<Rule>) -> Special {
+        let mut inner = pair.into_inner();
+
+        Special::While {
+            condition: Node::parse_value(
+                inner.next().expect("Rule::while_ should have condition"),
+            ),
+            body: Self::body(
+                inner.next().expect("Rule::while_ did not have body."),
+            ),
+        }
+    }
+
+    fn dotimes(pair: Pair<Rule>) -> Special {
+        let mut inner = pair.into_inner();
+
+        Special::Dotimes {
+            name: inner
+                .next()
+                .expect("Rule::dotimes did not have name")
+                .as_str()
+                .to_owned(),
+            count: Node::parse_value(
+                inner.next().expect("Rule::dotimes did not have count"),
+            ),
+            body: Self::body(
+                inner.next().expect("Rule::dotimes did not have body."),
+            ),
+        }
+    }
+
+    fn try_(pair: Pair<Rule>) -> Special {
+        let mut inner = pair.into_inner();
+
+        let body =
+            Self::body(inner.next().expect("Rule::try_ did not have body."));
+
+        let mut catch = None;
+        let mut finally = None;
+
+        for clause in inner {
+            match clause.as_rule() {
+                Rule::catch_clause => {
+                    let mut inner = clause.into_inner();
+
+                    let name = inner
+                        .next()
+                        .expect("Rule::catch_clause did not have name.")
+                        .as_str()
+                        .to_owned();
+
+                    catch =
+                        Some((name, inner.map(Node::parse_value).collect()));
+                },
+                Rule::finally_clause => finally = Some(Self::body(clause)),
+                other => {
+                    unreachable!("Encountered {other:?} inside Rule::try_.")
+                },
+            }
+        }
+
+        Special::Try { body, catch, finally }
     }
 
     fn set(pair: Pair<Rule>) -> Special {
@@ -285,6 +848,39 @@ impl Special {
         }
     }
 
+    fn bindings_and_body(
+        pair: Pair<Rule>,
+    ) -> (Vec<(Pattern, Node)>, Vec<Node>) {
+        let mut inner = pair.into_inner();
+
+        let bindings = inner
+            .next()
+            .expect("Special form did not have bindings.")
+            .into_inner()
+            .map(|binding| {
+                let mut inner = binding.into_inner();
+
+                let pattern = Pattern::from_pair(
+                    inner.next().expect("Rule::binding did not have pattern."),
+                );
+                let value = Node::parse_value(
+                    inner.next().expect("Rule::binding did not have value."),
+                );
+
+                (pattern, value)
+            })
+            .collect();
+
+        let body =
+            Self::body(inner.next().expect("Special form did not have body."));
+
+        (bindings, body)
+    }
+
+    fn body(pair: Pair<Rule>) -> Vec<Node> {
+        pair.into_inner().map(Node::parse_value).collect()
+    }
+
     fn function(mut pairs: Pairs<Rule>) -> Special {
         Special::Fn {
             parameters: parse_parameters(
@@ -333,8 +929,13 @@ fn parameter(pair: Pair<Rule>, optional: bool) -> Parameter {
             match p.as_str() {
                 "bool" => ParameterType::Boolean,
                 "number" => ParameterType::Number,
+                "int" => ParameterType::Integer,
+                "float" => ParameterType::Float,
                 "string" => ParameterType::String,
+                "symbol" => ParameterType::Symbol,
+                "keyword" => ParameterType::Keyword,
                 "list" => ParameterType::List,
+                "map" => ParameterType::Map,
                 "module" => ParameterType::Module,
                 "function" => ParameterType::Function,
                 "nil" => ParameterType::Nil,
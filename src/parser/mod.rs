@@ -4,3 +4,5 @@ pub struct Parser;
 
 pub mod ast;
 pub mod parameters;
+pub mod pattern;
+pub(crate) mod string;
@@ -42,7 +42,7 @@ impl Parameters {
 
     pub fn has_rest_param(&self) -> bool {
         // If a `Parameters` object has a rest param, it's always the last one.
-        self.parameters.last().map_or(false, Parameter::is_rest_param)
+        self.parameters.last().is_some_and(Parameter::is_rest_param)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -53,7 +53,7 @@ impl Parameters {
         self.parameters.len()
     }
 
-    pub fn iter(&self) -> std::slice::Iter<Parameter> {
+    pub fn iter(&self) -> std::slice::Iter<'_, Parameter> {
         self.parameters.iter()
     }
 
@@ -61,6 +61,42 @@ impl Parameters {
         self.parameters.last()
     }
 
+    /// Returns the parameters as they're written in source, e.g.
+    /// `["a", "b:string", "&opt", "c"]`.
+    pub(crate) fn to_tokens(&self) -> Vec<String> {
+        let mut args = self
+            .parameters
+            .iter()
+            .map(|p| {
+                if p.types().is_empty() {
+                    p.get_name().to_owned()
+                } else {
+                    format!(
+                        "{}:{}",
+                        p.name(),
+                        p.types()
+                            .iter()
+                            .map(std::string::ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("|")
+                    )
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(first_optional_index) =
+            self.parameters.iter().position(Parameter::is_optional)
+        {
+            args.insert(first_optional_index, "&opt".to_owned());
+        }
+
+        if self.parameters.iter().last().is_some_and(Parameter::is_rest_param) {
+            args.insert(args.len() - 1, "&".to_owned());
+        }
+
+        args
+    }
+
     fn rest_param_is_not_last_param(parameters: &[Parameter]) -> bool {
         parameters.iter().enumerate().any(|(i, param)| {
             param.is_rest_param() && i != parameters.len() - 1
@@ -83,7 +119,10 @@ impl TryFrom<&str> for Parameters {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self> {
-        let pairs = Parser::parse(Rule::function_parameters, value)?
+        let pairs = Parser::parse(Rule::parameters_main, value)?
+            .next()
+            .unwrap()
+            .into_inner()
             .next()
             .unwrap()
             .into_inner();
@@ -92,40 +131,18 @@ impl TryFrom<&str> for Parameters {
     }
 }
 
-impl std::fmt::Display for Parameters {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut args = self
-            .parameters
-            .iter()
-            .map(|p| {
-                if p.types().is_empty() {
-                    p.get_name().to_owned()
-                } else {
-                    format!(
-                        "{}:{}",
-                        p.name(),
-                        p.types()
-                            .iter()
-                            .map(std::string::ToString::to_string)
-                            .collect::<Vec<_>>()
-                            .join("|")
-                    )
-                }
-            })
-            .collect::<Vec<_>>();
-
-        if let Some(first_optional_index) =
-            self.parameters.iter().position(Parameter::is_optional)
-        {
-            args.insert(first_optional_index, "&opt".to_owned());
-        }
+impl<'a> IntoIterator for &'a Parameters {
+    type IntoIter = std::slice::Iter<'a, Parameter>;
+    type Item = &'a Parameter;
 
-        if self.parameters.iter().last().map_or(false, Parameter::is_rest_param)
-        {
-            args.insert(args.len() - 1, "&".to_owned());
-        }
+    fn into_iter(self) -> Self::IntoIter {
+        self.parameters.iter()
+    }
+}
 
-        write!(f, "[{}]", args.join(" "))
+impl std::fmt::Display for Parameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}]", self.to_tokens().join(" "))
     }
 }
 
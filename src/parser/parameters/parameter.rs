@@ -27,6 +27,12 @@ impl Parameter {
         &self.name
     }
 
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
     #[must_use]
     pub fn module(mut self) -> Self {
         self.parameter_types.push(ParameterType::Module);
@@ -6,9 +6,12 @@ use crate::Result;
 pub enum ParameterType {
     TypedList(Box<ParameterType>),
     List,
+    Map,
     Module,
     Function,
     Number,
+    Integer,
+    Float,
     String,
     Boolean,
     Symbol,
@@ -24,7 +27,12 @@ impl ParameterType {
             },
             ParameterType::Function => matches!(value, Value::Callable(_)),
             ParameterType::List => matches!(value, Value::List(_)),
-            ParameterType::Number => matches!(value, Value::Number(_)),
+            ParameterType::Map => matches!(value, Value::Map(_)),
+            ParameterType::Number => {
+                matches!(value, Value::Integer(_) | Value::Float(_))
+            },
+            ParameterType::Integer => matches!(value, Value::Integer(_)),
+            ParameterType::Float => matches!(value, Value::Float(_)),
             ParameterType::String => matches!(value, Value::String(_)),
             ParameterType::Boolean => matches!(value, Value::Boolean(_)),
             ParameterType::Symbol => matches!(value, Value::Symbol(_)),
@@ -48,7 +56,10 @@ impl TryFrom<&str> for ParameterType {
         let ptype = match string {
             "string" => Self::String,
             "number" => Self::Number,
+            "int" => Self::Integer,
+            "float" => Self::Float,
             "list" => Self::List,
+            "map" => Self::Map,
             "bool" | "boolean" => Self::Boolean,
             other => {
                 return Err(
@@ -67,9 +78,12 @@ impl std::fmt::Display for ParameterType {
             ParameterType::TypedList(_) | ParameterType::List => {
                 write!(f, "list")
             },
+            ParameterType::Map => write!(f, "map"),
             ParameterType::Module => write!(f, "module"),
             ParameterType::Function => write!(f, "function"),
             ParameterType::Number => write!(f, "number"),
+            ParameterType::Integer => write!(f, "int"),
+            ParameterType::Float => write!(f, "float"),
             ParameterType::String => write!(f, "string"),
             ParameterType::Boolean => write!(f, "bool"),
             ParameterType::Symbol => write!(f, "symbol"),
@@ -0,0 +1,139 @@
+use pest::iterators::Pair;
+
+use crate::interpreter::Value;
+use crate::parser::ast::{Node, NodeData};
+use crate::parser::Rule;
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Symbol(String),
+    Literal(Value),
+    List { items: Vec<Pattern>, rest: Option<String> },
+}
+
+impl Pattern {
+    pub fn from_pair(pair: Pair<Rule>) -> Pattern {
+        match pair.as_rule() {
+            Rule::token => Pattern::Symbol(pair.as_str().to_owned()),
+            Rule::constant
+            | Rule::integer
+            | Rule::float
+            | Rule::keyword
+            | Rule::string => {
+                Pattern::Literal(Self::literal(&Node::parse_value(pair)))
+            },
+            Rule::list_pattern => {
+                let mut items = Vec::new();
+                let mut rest = None;
+
+                for inner in pair.into_inner() {
+                    if inner.as_rule() == Rule::rest_pattern {
+                        rest = Some(
+                            inner
+                                .into_inner()
+                                .next()
+                                .expect("Rule::rest_pattern should have name.")
+                                .as_str()
+                                .to_owned(),
+                        );
+                    } else {
+                        items.push(Pattern::from_pair(inner));
+                    }
+                }
+
+                Pattern::List { items, rest }
+            },
+            rule => unreachable!("Encountered {rule:?} inside pattern."),
+        }
+    }
+
+    fn literal(node: &Node) -> Value {
+        match node.data() {
+            NodeData::Keyword(keyword) => Value::Keyword(keyword.clone()),
+            NodeData::Integer(integer) => Value::Integer(*integer),
+            NodeData::Float(float) => Value::Float(*float),
+            NodeData::String(string) => Value::String(string.clone()),
+            NodeData::True => Value::Boolean(true),
+            NodeData::False => Value::Boolean(false),
+            NodeData::Nil => Value::Nil,
+            other => unreachable!("Encountered {other:?} as literal pattern."),
+        }
+    }
+
+    /// Matches `value` against this pattern, returning the bindings it
+    /// creates, or `None` if `value` does not match.
+    pub fn destructure(&self, value: &Value) -> Option<Vec<(String, Value)>> {
+        let mut bindings = Vec::new();
+
+        if self.destructure_into(value, &mut bindings) {
+            Some(bindings)
+        } else {
+            None
+        }
+    }
+
+    fn destructure_into(
+        &self,
+        value: &Value,
+        bindings: &mut Vec<(String, Value)>,
+    ) -> bool {
+        match self {
+            // `_` matches anything without binding it.
+            Pattern::Symbol(name) if name == "_" => true,
+            Pattern::Symbol(name) => {
+                bindings.push((name.clone(), value.clone()));
+                true
+            },
+            Pattern::Literal(literal) => literal == value,
+            Pattern::List { items, rest } => {
+                let Value::List(values) = value else {
+                    return false;
+                };
+
+                let length_matches = if rest.is_some() {
+                    values.len() >= items.len()
+                } else {
+                    values.len() == items.len()
+                };
+
+                if !length_matches {
+                    return false;
+                }
+
+                for (item, value) in items.iter().zip(values) {
+                    if !item.destructure_into(value, bindings) {
+                        return false;
+                    }
+                }
+
+                if let Some(rest) = rest {
+                    bindings.push((
+                        rest.clone(),
+                        Value::List(values[items.len()..].to_vec()),
+                    ));
+                }
+
+                true
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Symbol(name) => write!(f, "{name}"),
+            Pattern::Literal(literal) => write!(f, "{}", literal.repl_repr()),
+            Pattern::List { items, rest } => {
+                let mut strings =
+                    items.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+                if let Some(rest) = rest {
+                    strings.push(format!("& {rest}"));
+                }
+
+                write!(f, "[{}]", strings.join(" "))
+            },
+        }
+    }
+}
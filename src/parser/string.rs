@@ -0,0 +1,71 @@
+/// Decodes the escape sequences accepted by the grammar.
+///
+/// Returns a message describing the problem if an escape doesn't encode a
+/// valid character, e.g. a surrogate in `\uD800`.
+pub(crate) fn unescape(text: &str) -> Result<String, String> {
+    let mut string = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some('u') => unescape_unicode(&mut chars)?,
+            other => {
+                unreachable!("Grammar accepted invalid escape '\\{other:?}'.")
+            },
+        };
+
+        string.push(escaped);
+    }
+
+    Ok(string)
+}
+
+/// Escapes `text` so it can be written inside a string literal.
+pub(crate) fn escape(text: &str) -> String {
+    let mut string = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            '\0' => string.push_str("\\0"),
+            c => string.push(c),
+        }
+    }
+
+    string
+}
+
+// Reads the code point of either `\uXXXX` or `\u{X...}`, after the `\u`.
+fn unescape_unicode(chars: &mut std::str::Chars) -> Result<char, String> {
+    let hex: String = if chars.as_str().starts_with('{') {
+        chars.next();
+        chars.by_ref().take_while(|c| *c != '}').collect()
+    } else {
+        chars.by_ref().take(4).collect()
+    };
+
+    let code_point = u32::from_str_radix(&hex, 16)
+        .expect("Grammar should only accept hexadecimal digits.");
+
+    char::from_u32(code_point).ok_or_else(|| {
+        format!("'\\u{{{hex}}}' is not a valid unicode character.")
+    })
+}
@@ -1,21 +1,81 @@
 use crate::location::Location;
-use crate::parser::ast::Node;
+use crate::parser::ast::{ImportFilter, Node};
 use crate::parser::parameters::Parameters;
+use crate::parser::pattern::Pattern;
 
 pub trait Visitor<T: std::fmt::Debug> {
     fn visit_node(&mut self, node: &Node) -> T;
     fn visit_main(&mut self, nodes: &[Node]) -> T;
     fn visit_fn(&mut self, parameters: &Parameters, body: &[Node]) -> T;
+    fn visit_defmacro(
+        &mut self,
+        name: &str,
+        parameters: &Parameters,
+        body: &[Node],
+        location: Location,
+    ) -> T;
+    fn visit_do(&mut self, body: &[Node]) -> T;
     fn visit_if(
         &mut self,
         condition: &Node,
         then: &Node,
         else_branch: Option<&Node>,
     ) -> T;
-    fn visit_import(&mut self, name: &str, prefix: Option<&String>) -> T;
+    fn visit_and(&mut self, values: &[Node]) -> T;
+    fn visit_or(&mut self, values: &[Node]) -> T;
+    fn visit_nil_coalesce(&mut self, values: &[Node]) -> T;
+    fn visit_cond(&mut self, clauses: &[(Node, Node)]) -> T;
+    fn visit_match(
+        &mut self,
+        value: &Node,
+        clauses: &[(Pattern, Node)],
+        location: Location,
+    ) -> T;
+    fn visit_import(
+        &mut self,
+        name: &str,
+        prefix: Option<&String>,
+        filter: Option<&ImportFilter>,
+        rename: &[(String, String)],
+        location: Location,
+    ) -> T;
+    fn visit_let(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        location: Location,
+    ) -> T;
+    fn visit_loop(
+        &mut self,
+        bindings: &[(Pattern, Node)],
+        body: &[Node],
+        location: Location,
+    ) -> T;
+    fn visit_recur(&mut self, arguments: &[Node], location: Location) -> T;
+    fn visit_while(&mut self, condition: &Node, body: &[Node]) -> T;
+    fn visit_dotimes(
+        &mut self,
+        name: &str,
+        count: &Node,
+        body: &[Node],
+        location: Location,
+    ) -> T;
+    fn visit_try(
+        &mut self,
+        body: &[Node],
+        catch: Option<&(String, Vec<Node>)>,
+        finally: Option<&Vec<Node>>,
+    ) -> T;
     fn visit_set(&mut self, name: &str, value: &Node, location: Location) -> T;
     fn visit_var(&mut self, name: &str, value: &Node, location: Location) -> T;
+    fn visit_quote(&mut self, datum: &Node) -> T;
+    fn visit_quasiquote(&mut self, datum: &Node) -> T;
+    fn visit_unquote(&mut self, value: &Node, location: Location) -> T;
+    fn visit_unquote_splicing(&mut self, value: &Node, location: Location)
+        -> T;
     fn visit_list(&mut self, literal: bool, nodes: &[Node]) -> T;
+    fn visit_map(&mut self, entries: &[(Node, Node)]) -> T;
+    fn visit_fstring(&mut self, pieces: &[Node]) -> T;
     fn visit_symbol(
         &mut self,
         module: Option<&String>,
@@ -0,0 +1,27 @@
+mod common;
+
+use common::{eval, output};
+
+#[test]
+fn and_or_short_circuit() {
+    let source = r#"
+        (and false (println "and"))
+        (or 1 (println "or"))
+        (?? 1 (println "??"))"#;
+
+    assert_eq!(output(source), "");
+}
+
+#[test]
+fn and_or_return_deciding_value() {
+    assert_eq!(eval("[(and 1 nil 2) (and 1 2) (and)]"), "(nil 2 true)");
+    assert_eq!(eval("[(or nil false) (or nil 2 3) (or)]"), "(false 2 nil)");
+    assert_eq!(eval("[(?? nil false) (?? nil nil)]"), "(false nil)");
+}
+
+#[test]
+fn and_or_can_be_passed_as_functions() {
+    assert_eq!(eval("(apply or [nil false 3])"), "3");
+    assert_eq!(eval("(list/reduce and true [1 2 nil])"), "nil");
+    assert_eq!(eval("(apply ?? [nil :default])"), ":default");
+}
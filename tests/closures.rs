@@ -0,0 +1,47 @@
+mod common;
+
+use common::eval;
+
+#[test]
+fn closure_captures_defining_environment() {
+    let source = r"
+        (defn adder [n] (fn [x] (+ x n)))
+        (var add-two (adder 2))
+        (add-two 40)";
+
+    assert_eq!(eval(source), "42");
+}
+
+#[test]
+fn counters_keep_separate_state() {
+    let source = r"
+        (defn counter []
+            (var count 0)
+            (fn [] (set count (+ count 1)) count))
+        (var a (counter))
+        (var b (counter))
+        (a) (a) (b)
+        [(a) (b)]";
+
+    assert_eq!(eval(source), "(3 2)");
+}
+
+#[test]
+fn closure_sees_later_changes_to_captured_variable() {
+    let source = r"
+        (var x 1)
+        (defn get-x [] x)
+        (set x 2)
+        (get-x)";
+
+    assert_eq!(eval(source), "2");
+}
+
+#[test]
+fn recursive_function_refers_to_itself() {
+    let source = r"
+        (defn fact [n] (if (<= n 1) 1 (* n (fact (- n 1)))))
+        (fact 10)";
+
+    assert_eq!(eval(source), "3628800");
+}
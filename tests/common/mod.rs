@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+#![allow(clippy::result_large_err)]
+
+use tapr::{Interpreter, NativeModules, TaprError, Value};
+use tempfile::TempDir;
+
+/// Creates an interpreter with the built-in modules, which writes its output
+/// to `output`.
+pub fn interpreter(output: &mut Vec<u8>) -> Interpreter<'_> {
+    let environment = NativeModules::builtin()
+        .environment()
+        .expect("Built-in modules should not define a name twice.");
+
+    Interpreter::new(Box::new(output), environment)
+}
+
+pub fn try_eval(source: &str) -> Result<Value, TaprError> {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret(source, "<test>")
+}
+
+/// Evaluates `source` and returns the value it evaluates to, as it's printed.
+pub fn eval(source: &str) -> String {
+    match try_eval(source) {
+        Ok(value) => value.to_string(),
+        Err(error) => panic!("Unable to evaluate {source:?}:\n{error}"),
+    }
+}
+
+/// Evaluates `source` and returns the error it fails with.
+pub fn eval_err(source: &str) -> TaprError {
+    match try_eval(source) {
+        Ok(value) => panic!("Expected {source:?} to fail, got '{value}'."),
+        Err(error) => error,
+    }
+}
+
+/// Evaluates `source` and returns what it printed.
+pub fn output(source: &str) -> String {
+    let mut output = Vec::new();
+
+    if let Err(error) = interpreter(&mut output).interpret(source, "<test>") {
+        panic!("Unable to evaluate {source:?}:\n{error}");
+    }
+
+    String::from_utf8(output).expect("Output should be valid UTF-8.")
+}
+
+/// Writes `files` into a new directory and interprets `main.tapr` in it.
+/// Returns what it printed.
+pub fn run_files(files: &[(&str, &str)]) -> Result<String, TaprError> {
+    let dir = TempDir::new().expect("Unable to create temporary directory.");
+
+    for (name, source) in files {
+        let path = dir.path().join(name);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("Unable to create dir.");
+        }
+
+        std::fs::write(path, source).expect("Unable to write file.");
+    }
+
+    let mut output = Vec::new();
+
+    interpreter(&mut output).interpret_file(&dir.path().join("main.tapr"))?;
+
+    Ok(String::from_utf8(output).expect("Output should be valid UTF-8."))
+}
@@ -0,0 +1,22 @@
+mod common;
+
+use common::eval;
+
+#[test]
+fn comparisons_compare_left_to_right() {
+    let source = "[(> 2 1) (>= 1 1) (< 1 2) (<= 2 1) (> 1 2) (< 2 1)]";
+
+    assert_eq!(eval(source), "(true true true false false false)");
+}
+
+#[test]
+fn comparisons_check_every_adjacent_pair() {
+    let source = "[(< 1 2 3) (< 1 3 2) (> 3 1 2) (>= 3 3 1) (<= 1 2 2 1)]";
+
+    assert_eq!(eval(source), "(true false false true false)");
+}
+
+#[test]
+fn comparisons_mix_integers_and_floats() {
+    assert_eq!(eval("[(< 1 1.5 2) (>= 2.0 2)]"), "(true true)");
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{eval, eval_err};
+use tapr::TaprErrorKind;
+
+#[test]
+fn let_binds_sequentially() {
+    assert_eq!(eval("(let [a 1 b (+ a 1)] (* a b))"), "2");
+}
+
+#[test]
+fn let_destructures_lists() {
+    assert_eq!(eval("(let [[a b] [1 2]] (+ a b))"), "3");
+}
+
+#[test]
+fn let_destructures_nested_lists_and_rest() {
+    let source = "(let [[a [b c] & rest] [1 [2 3] 4 5]] [a b c rest])";
+
+    assert_eq!(eval(source), "(1 2 3 (4 5))");
+}
+
+#[test]
+fn let_bindings_do_not_leak() {
+    let error = eval_err("(let [a 1] a) a");
+
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+}
+
+#[test]
+fn let_fails_on_mismatched_pattern() {
+    let error = eval_err("(let [[a b] [1]] a)");
+
+    assert!(matches!(error.kind, TaprErrorKind::PatternMismatch { .. }));
+}
+
+#[test]
+fn match_picks_first_matching_clause() {
+    let source = r#"
+        (defn describe [value]
+            (match value
+                0 "zero"
+                [x] "one"
+                [x y] (str "two " x " " y)
+                [x & rest] "many"
+                :default "other"
+                _ "anything"))
+        [(describe 0) (describe [1]) (describe [1 2]) (describe [1 2 3])
+         (describe :default) (describe 5)]"#;
+
+    assert_eq!(eval(source), "(zero one two 1 2 many other anything)");
+}
+
+#[test]
+fn match_without_matching_clause_is_nil() {
+    assert_eq!(eval("(match 1 2 :two)"), "nil");
+}
+
+#[test]
+fn cond_picks_first_true_clause() {
+    assert_eq!(eval("(cond false 1 (== 1 2) 2 true 3)"), "3");
+}
@@ -0,0 +1,482 @@
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::interpreter;
+use tapr::{
+    Environment, Interpreter, NativeFunction, NativeModule, NativeModules,
+    TaprErrorKind, TaprResult, Value,
+};
+use tempfile::TempDir;
+
+#[test]
+fn native_closure_updates_host_state() {
+    let count = Arc::new(Mutex::new(0));
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    let counter = Arc::clone(&count);
+    intp.define_native("tick", "n:int", move |_, arguments| {
+        let mut count = counter.lock().unwrap();
+        *count += arguments.unwrap_integer(0);
+
+        Ok(Value::Integer(*count))
+    })
+    .unwrap();
+
+    let value = intp.interpret("(tick 2) (tick 3)", "<test>").unwrap();
+
+    assert_eq!(value.to_string(), "5");
+    assert_eq!(*count.lock().unwrap(), 5);
+}
+
+#[test]
+fn native_parameters_are_checked() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_native("twice", "n:number", |_, arguments| {
+        Ok(Value::Float(arguments.unwrap_number(0) * 2.0))
+    })
+    .unwrap();
+
+    let error = intp.interpret(r#"(twice "2")"#, "<test>").unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::InvalidArgument { .. }));
+
+    let error = intp.interpret("(twice 1 2)", "<test>").unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::WrongAmountOfFixedArgs { .. }));
+}
+
+#[test]
+fn native_rest_parameters_collect_arguments() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_native("count-args", "& values", |_, arguments| {
+        let count = arguments.unwrap_from(0).len();
+
+        Ok(Value::Integer(count.try_into().unwrap()))
+    })
+    .unwrap();
+
+    let value = intp.interpret("[(count-args) (count-args 1 2 3)]", "<test>");
+
+    assert_eq!(value.unwrap().to_string(), "(0 3)");
+}
+
+#[test]
+fn invalid_native_parameters_are_rejected() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    let error =
+        intp.define_native("bad", "n:nope", |_, _| Ok(Value::Nil)).unwrap_err();
+
+    assert!(matches!(error.kind, TaprErrorKind::ParserError { .. }));
+}
+
+#[test]
+fn native_can_call_back_into_interpreter() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_native("call-twice", "f:function x", |intp, arguments| {
+        let function = arguments.unwrap(0);
+        let value = intp.call(&function, vec![arguments.unwrap(1)])?;
+
+        intp.call(&function, vec![value])
+    })
+    .unwrap();
+
+    let value = intp.interpret("(call-twice (fn [x] (* x 3)) 2)", "<test>");
+
+    assert_eq!(value.unwrap().to_string(), "18");
+}
+
+#[test]
+fn native_is_visible_in_imported_modules() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("lib.tapr"), "(defn f [] (host-value))")
+        .unwrap();
+    std::fs::write(dir.path().join("main.tapr"), "(import lib) (lib/f)")
+        .unwrap();
+
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_native("host-value", "", |_, _| Ok(Value::Integer(42)))
+        .unwrap();
+
+    let value = intp.interpret_file(&dir.path().join("main.tapr")).unwrap();
+
+    assert_eq!(value.to_string(), "42");
+}
+
+#[test]
+fn native_can_not_be_defined_twice() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_native("f", "", |_, _| Ok(Value::Nil)).unwrap();
+    let error = intp.define_native("f", "", |_, _| Ok(Value::Nil)).unwrap_err();
+
+    assert!(matches!(error.kind, TaprErrorKind::SymbolDefined(_)));
+}
+
+#[test]
+fn get_global_returns_script_definitions() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret("(var answer 42)", "<test>").unwrap();
+
+    assert_eq!(intp.get_global("answer").unwrap().to_string(), "42");
+    assert!(matches!(intp.get_global("println").unwrap(), Value::Callable(_)));
+}
+
+#[test]
+fn get_global_suggests_similar_names() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret("(var answer 42)", "<test>").unwrap();
+
+    let error = intp.get_global("answr").unwrap_err();
+
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+    assert!(error.hint.unwrap().contains("answer"));
+}
+
+#[test]
+fn set_global_defines_and_replaces_values() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.set_global("limit", Value::Integer(1)).unwrap();
+    intp.interpret("(defn over-limit? [n] (> n limit))", "<test>").unwrap();
+    intp.set_global("limit", Value::Integer(10)).unwrap();
+
+    let value = intp.interpret("[limit (over-limit? 5)]", "<test>");
+
+    assert_eq!(value.unwrap().to_string(), "(10 false)");
+}
+
+#[test]
+fn call_by_name_calls_script_function() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret("(defn add [a b] (+ a b))", "<test>").unwrap();
+
+    let value = intp
+        .call_by_name("add", vec![Value::Integer(40), Value::Integer(2)])
+        .unwrap();
+
+    assert_eq!(value.to_string(), "42");
+}
+
+#[test]
+fn call_by_name_checks_arguments() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret("(defn add [a:number b:number] (+ a b))", "<test>").unwrap();
+
+    let error = intp.call_by_name("add", vec![Value::Integer(1)]).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::WrongAmountOfFixedArgs { .. }));
+
+    let error = intp
+        .call_by_name("add", vec![Value::Nil, Value::Integer(1)])
+        .unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::InvalidArgument { .. }));
+}
+
+#[test]
+fn call_by_name_fails_for_undefined_and_non_functions() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret("(var answer 42)", "<test>").unwrap();
+
+    let error = intp.call_by_name("missing", Vec::new()).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+
+    let error = intp.call_by_name("answer", Vec::new()).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::NotCallable(_)));
+}
+
+#[test]
+fn call_rejects_macros() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.interpret("(defmacro twice [x] `(do ,x ,x))", "<test>").unwrap();
+
+    let error = intp.call_by_name("twice", vec![Value::Nil]).unwrap_err();
+
+    assert!(matches!(error.kind, TaprErrorKind::MacroNotExpanded(_)));
+}
+
+#[test]
+fn call_looks_up_keywords_in_maps() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    let map = intp.interpret("{:a 1}", "<test>").unwrap();
+    let keyword = Value::Keyword("a".to_owned());
+
+    assert_eq!(intp.call(&keyword, vec![map]).unwrap().to_string(), "1");
+}
+
+#[test]
+fn call_runs_closures_with_their_environment() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    let adder = intp
+        .interpret("(defn adder [n] (fn [x] (+ x n))) (adder 2)", "<test>")
+        .unwrap();
+
+    let value = intp.call(&adder, vec![Value::Integer(40)]).unwrap();
+
+    assert_eq!(value.to_string(), "42");
+}
+
+#[test]
+fn call_reports_tail_calls_and_script_errors() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    let source = r"
+        (defn count-down [n] (if (== n 0) :done (count-down (- n 1))))
+        (defn fail [] (throw :oops))";
+    intp.interpret(source, "<test>").unwrap();
+
+    let value = intp.call_by_name("count-down", vec![Value::Integer(100_000)]);
+    assert_eq!(value.unwrap().to_string(), ":done");
+
+    let error = intp.call_by_name("fail", Vec::new()).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::Thrown(_)));
+}
+
+#[test]
+fn define_fn_converts_arguments_and_result() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_fn("repeat", |string: String, count: usize| {
+        Ok(string.repeat(count))
+    })
+    .unwrap();
+
+    let value = intp.interpret(r#"(repeat "ab" 3)"#, "<test>");
+
+    assert_eq!(value.unwrap().to_string(), "ababab");
+}
+
+#[test]
+fn define_fn_trailing_options_are_optional() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_fn("add", |a: i64, b: Option<i64>| Ok(a + b.unwrap_or(1)))
+        .unwrap();
+
+    let value = intp.interpret("[(add 1) (add 1 2) (add 1 nil)]", "<test>");
+
+    assert_eq!(value.unwrap().to_string(), "(2 3 2)");
+}
+
+#[test]
+fn define_fn_converts_collections() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_fn("pairs", |values: Vec<i64>| {
+        Ok(values.into_iter().map(|v| (v, v * 2)).collect::<Vec<_>>())
+    })
+    .unwrap();
+    intp.define_fn("sum-pair", |(a, b): (f64, f64)| Ok(a + b)).unwrap();
+
+    let value = intp.interpret("[(pairs [1 2]) (sum-pair [1 0.5])]", "<test>");
+
+    assert_eq!(value.unwrap().to_string(), "(((1 2) (2 4)) 1.5)");
+}
+
+#[test]
+fn define_fn_reports_expected_types() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_fn("inc", |n: Option<i64>| Ok(n.unwrap_or(0) + 1)).unwrap();
+
+    let error = intp.interpret(r"(inc 1.5)", "<test>").unwrap_err();
+
+    assert!(matches!(error.kind, TaprErrorKind::InvalidArgument { .. }));
+    assert_eq!(
+        error.kind.to_string(),
+        "Invalid argument '1.5', expected int or nil."
+    );
+}
+
+#[test]
+fn define_fn_rejects_integers_that_do_not_fit() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_fn("byte", |n: u8| Ok(n)).unwrap();
+    intp.define_fn("huge", || Ok(u64::MAX)).unwrap();
+
+    let error = intp.interpret("(byte 300)", "<test>").unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::InvalidConversion { .. }));
+
+    let error = intp.interpret("(huge)", "<test>").unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::IntegerOutOfRange(_)));
+}
+
+#[test]
+fn define_fn_errors_propagate() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    intp.define_fn("fail", |value: Value| -> tapr::TaprResult<()> {
+        Err(TaprErrorKind::Thrown(value).into())
+    })
+    .unwrap();
+
+    let value = intp
+        .interpret("(try (fail :oops) (catch e (map/get e :value)))", "<test>");
+
+    assert_eq!(value.unwrap().to_string(), ":oops");
+}
+
+#[test]
+fn builtin_native_functions_have_named_parameters() {
+    let mut output = Vec::new();
+    let mut intp = interpreter(&mut output);
+
+    let value = intp.interpret("string/join", "<test>").unwrap();
+
+    assert!(value.to_string().contains("[separator:string strings:list]"));
+}
+
+/// A native module with a single function `tag`, which is a core module if
+/// `is_core` is set.
+struct Tags {
+    name: &'static str,
+    is_core: bool,
+}
+
+impl NativeModule for Tags {
+    fn environment(&self) -> Environment {
+        let environment = Environment::new();
+        let tag = NativeFunction::from_fn_named(
+            "tag",
+            |name: String| Ok(format!("<{name}>")),
+            &["name"],
+        );
+
+        environment.insert("tag".to_owned(), tag.into()).unwrap();
+
+        environment
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn is_core_module(&self) -> bool {
+        self.is_core
+    }
+}
+
+fn eval_with(modules: &NativeModules, source: &str) -> TaprResult<Value> {
+    let environment = modules.environment()?;
+    let mut output = Vec::new();
+
+    let mut intp = Interpreter::new(Box::new(&mut output), environment);
+
+    intp.interpret(source, "<test>")
+}
+
+#[test]
+fn empty_modules_define_nothing() {
+    let modules = NativeModules::empty();
+
+    assert!(modules.names().is_empty());
+
+    let error = eval_with(&modules, "(println 1)").unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+}
+
+#[test]
+fn custom_module_is_bound_under_its_name() {
+    let modules = NativeModules::empty()
+        .with_module(Tags { name: "tags", is_core: false });
+
+    let value = eval_with(&modules, r#"(tags/tag "b")"#).unwrap();
+    assert_eq!(value.to_string(), "<b>");
+
+    let error = eval_with(&modules, r#"(tag "b")"#).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+}
+
+#[test]
+fn custom_core_module_is_available_directly() {
+    let modules = NativeModules::builtin()
+        .with_module(Tags { name: "tags", is_core: true });
+
+    let value =
+        eval_with(&modules, r#"(string/join "" [(tag "a") (tag "b")])"#);
+
+    assert_eq!(value.unwrap().to_string(), "<a><b>");
+}
+
+#[test]
+fn with_module_replaces_module_of_same_name() {
+    let modules = NativeModules::builtin()
+        .with_module(Tags { name: "string", is_core: false });
+
+    assert_eq!(modules.names().iter().filter(|&&n| n == "string").count(), 1);
+
+    let value = eval_with(&modules, r#"(string/tag "a")"#).unwrap();
+    assert_eq!(value.to_string(), "<a>");
+
+    let error = eval_with(&modules, r#"(string/trim " a ")"#).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+}
+
+#[test]
+fn without_module_removes_builtin_module() {
+    let modules = NativeModules::builtin().without_module("fs");
+
+    assert!(!modules.names().contains(&"fs"));
+    assert!(modules.names().contains(&"string"));
+
+    let error = eval_with(&modules, r#"(fs/read_to_string "a")"#).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::ModuleNotDefined(_)));
+}
+
+#[test]
+fn with_core_makes_module_available_directly() {
+    let modules = NativeModules::builtin().with_core("string", true);
+
+    let value = eval_with(&modules, r#"(trim " a ")"#).unwrap();
+    assert_eq!(value.to_string(), "a");
+
+    let error = eval_with(&modules, r#"(string/trim " a ")"#).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::ModuleNotDefined(_)));
+}
+
+#[test]
+fn core_modules_can_not_define_same_name() {
+    let modules = NativeModules::empty()
+        .with_module(Tags { name: "tags", is_core: true })
+        .with_module(Tags { name: "more-tags", is_core: true });
+
+    let error = modules.environment().unwrap_err();
+
+    assert!(matches!(error.kind, TaprErrorKind::SymbolDefined(_)));
+}
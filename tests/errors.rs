@@ -0,0 +1,33 @@
+mod common;
+
+use common::eval;
+
+#[test]
+fn caught_error_is_map() {
+    let source = r"
+        (var e (try (throw :oops) (catch e e)))
+        [(map/get e :kind) (map/get e :message) (map/get e :value)]";
+
+    assert_eq!(eval(source), "(:thrown :oops :oops)");
+}
+
+#[test]
+fn caught_error_has_location() {
+    let source = r"(map/get (try (undefined-thing) (catch e e)) :location)";
+
+    assert_eq!(eval(source), "{:source <test> :line 1 :col 16}");
+}
+
+#[test]
+fn caught_error_kind_names_variant() {
+    let source = r"
+        (defn kind [f] (try (f) (catch e (map/get e :kind))))
+        [(kind (fn [] (undefined-thing)))
+         (kind (fn [] (1)))
+         (kind (fn [] [(recur)]))]";
+
+    assert_eq!(
+        eval(source),
+        "(:symbol-not-defined :not-callable :recur-not-in-tail-position)"
+    );
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::eval_err;
+
+fn hint(source: &str) -> Option<String> {
+    eval_err(source).hint
+}
+
+#[test]
+fn misspelled_symbol() {
+    let source = r"
+        (var counter 1)
+        (+ countr 1)";
+
+    assert_eq!(hint(source).as_deref(), Some("did you mean `counter`?"));
+}
+
+#[test]
+fn symbol_in_module() {
+    assert_eq!(
+        hint(r#"(join "," ["a"])"#).as_deref(),
+        Some("did you mean `string/join`?")
+    );
+}
+
+#[test]
+fn unqualified_symbol_is_preferred() {
+    let source = r"
+        (defn join [] nil)
+        (jon)";
+
+    assert_eq!(hint(source).as_deref(), Some("did you mean `join`?"));
+}
+
+#[test]
+fn misspelled_module() {
+    assert_eq!(
+        hint(r#"(strng/join "," ["a"])"#).as_deref(),
+        Some("did you mean `string`?")
+    );
+}
+
+#[test]
+fn misspelled_symbol_in_module() {
+    assert_eq!(
+        hint(r#"(string/jion "," ["a"])"#).as_deref(),
+        Some("did you mean `string/join`?")
+    );
+}
+
+#[test]
+fn misspelled_std_module() {
+    assert_eq!(
+        hint("(import std/colections)").as_deref(),
+        Some("did you mean `std/collections`?")
+    );
+}
+
+#[test]
+fn no_hint_for_distant_names() {
+    assert_eq!(hint("(completely-unknown-name)"), None);
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use std::path::Path;
+
+use common::run_files;
+use tapr::TaprErrorKind;
+
+fn run_ok(files: &[(&str, &str)]) -> String {
+    run_files(files)
+        .unwrap_or_else(|error| panic!("Unable to run files:\n{error}"))
+}
+
+#[test]
+fn imports_relative_to_main_file() {
+    let files = [
+        ("main.tapr", "(import lib/greet) (greet/hello)"),
+        ("lib/greet.tapr", r#"(defn hello [] (println "hello"))"#),
+    ];
+
+    assert_eq!(run_ok(&files), "hello\n");
+}
+
+#[test]
+fn imports_relative_to_importing_module() {
+    let files = [
+        ("main.tapr", "(import lib/a) (println (a/value))"),
+        ("lib/a.tapr", "(import b) (defn value [] (+ b/value 1))"),
+        ("lib/b.tapr", "(var value 41)"),
+        // Not imported, since `b` is resolved relative to `lib/a.tapr`.
+        ("b.tapr", "(var value 0)"),
+    ];
+
+    assert_eq!(run_ok(&files), "42\n");
+}
+
+#[test]
+fn modules_are_evaluated_once() {
+    let files = [
+        ("main.tapr", "(import a) (import b) (println a/value b/value)"),
+        ("a.tapr", "(import counter) (var value counter/value)"),
+        ("b.tapr", "(import counter) (var value counter/value)"),
+        ("counter.tapr", r#"(println "loading") (var value 1)"#),
+    ];
+
+    assert_eq!(run_ok(&files), "loading\n11\n");
+}
+
+#[test]
+fn circular_import_is_error() {
+    let files = [
+        ("main.tapr", "(import a)"),
+        ("a.tapr", "(import b)"),
+        ("b.tapr", "(import a)"),
+    ];
+
+    let error = run_files(&files).expect_err("Circular import should fail.");
+
+    let TaprErrorKind::CircularImport(chain) = error.kind else {
+        panic!("Expected CircularImport, got {:?}.", error.kind);
+    };
+
+    let names = chain
+        .iter()
+        .map(|path| Path::new(path).file_name().unwrap().to_string_lossy())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["a.tapr", "b.tapr", "a.tapr"]);
+}
+
+#[test]
+fn missing_module_is_error() {
+    let error = run_files(&[("main.tapr", "(import missing)")])
+        .expect_err("Missing module should fail.");
+
+    assert!(matches!(error.kind, TaprErrorKind::ModuleNotFound { .. }));
+}
+
+#[test]
+fn private_names_are_not_imported() {
+    let files = [
+        ("main.tapr", "(import lib :as \"\") (println (public))"),
+        ("lib.tapr", "(var _secret 1) (defn public [] (+ _secret 1))"),
+    ];
+
+    assert_eq!(run_ok(&files), "2\n");
+
+    let files = [
+        ("main.tapr", "(import lib :as \"\") _secret"),
+        ("lib.tapr", "(var _secret 1)"),
+    ];
+
+    let error = run_files(&files).expect_err("_secret should be undefined.");
+
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+}
+
+#[test]
+fn private_names_are_not_accessible() {
+    let files = [
+        ("main.tapr", "(import lib) lib/_secret"),
+        ("lib.tapr", "(var _secret 1)"),
+    ];
+
+    let error = run_files(&files).expect_err("lib/_secret should be private.");
+
+    assert!(matches!(error.kind, TaprErrorKind::PrivateSymbol { .. }));
+}
+
+#[test]
+fn private_names_can_not_be_imported_explicitly() {
+    let files = [
+        ("main.tapr", "(import lib :only [_secret])"),
+        ("lib.tapr", "(var _secret 1)"),
+    ];
+
+    let error = run_files(&files).expect_err("_secret should be private.");
+
+    assert!(matches!(error.kind, TaprErrorKind::PrivateSymbol { .. }));
+}
+
+#[test]
+fn lsmod_hides_private_names() {
+    let files = [
+        ("main.tapr", "(import lib) (debug/lsmod lib)"),
+        ("lib.tapr", "(var _a-long-private-name 1) (var b 2)"),
+    ];
+
+    assert_eq!(run_ok(&files), "+---+---+\n| b | 2 |\n+---+---+\n");
+}
+
+#[test]
+fn env_shows_own_private_names() {
+    let files = [("main.tapr", "(var _own 1) (debug/env)")];
+
+    assert!(run_ok(&files).contains("| _own |"));
+}
+
+#[test]
+fn rename_alone_keeps_module_prefix() {
+    let files = [
+        ("main.tapr", "(import lib :rename {a c}) (println c lib/a lib/b)"),
+        ("lib.tapr", "(var a 1) (var b 2)"),
+    ];
+
+    assert_eq!(run_ok(&files), "112\n");
+
+    let files = [
+        ("main.tapr", "(import lib :rename {a c}) b"),
+        ("lib.tapr", "(var a 1) (var b 2)"),
+    ];
+
+    let error = run_files(&files).expect_err("b should not be imported.");
+
+    assert!(matches!(error.kind, TaprErrorKind::SymbolNotDefined(_)));
+}
+
+#[test]
+fn rename_with_only() {
+    let files = [
+        ("main.tapr", "(import lib :only [a b] :rename {a c}) (println c b)"),
+        ("lib.tapr", "(var a 1) (var b 2)"),
+    ];
+
+    assert_eq!(run_ok(&files), "12\n");
+}
+
+#[test]
+fn rename_with_empty_prefix_imports_everything() {
+    let files = [
+        ("main.tapr", "(import lib :as \"\" :rename {a c}) (println c b)"),
+        ("lib.tapr", "(var a 1) (var b 2)"),
+    ];
+
+    assert_eq!(run_ok(&files), "12\n");
+}
+
+#[test]
+fn exclude_imports_the_rest() {
+    let files = [
+        ("main.tapr", "(import lib :exclude [a]) (println b)"),
+        ("lib.tapr", "(var a 1) (var b 2)"),
+    ];
+
+    assert_eq!(run_ok(&files), "2\n");
+}
+
+#[test]
+fn renaming_name_that_is_not_imported_is_error() {
+    let files = [
+        ("main.tapr", "(import lib :only [b] :rename {a c})"),
+        ("lib.tapr", "(var a 1) (var b 2)"),
+    ];
+
+    let error = run_files(&files).expect_err("a is not imported.");
+
+    assert!(matches!(error.kind, TaprErrorKind::InvalidImport { .. }));
+}
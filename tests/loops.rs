@@ -0,0 +1,87 @@
+mod common;
+
+use common::{eval, eval_err, output};
+use tapr::TaprErrorKind;
+
+#[test]
+fn while_runs_until_condition_is_false() {
+    let source = r"
+        (var n 0)
+        (var runs [])
+        (while (< n 3)
+            (set runs (list/push runs n))
+            (set n (+ n 1)))
+        [runs n]";
+
+    assert_eq!(eval(source), "((0 1 2) 3)");
+}
+
+#[test]
+fn while_with_false_condition_never_runs_body() {
+    let source = r#"(while false (println "body")) (println "done")"#;
+
+    assert_eq!(output(source), "done\n");
+    assert_eq!(eval("(while nil 1)"), "nil");
+}
+
+#[test]
+fn while_body_has_its_own_scope() {
+    let source = r"
+        (var n 0)
+        (while (< n 2) (var step 1) (set n (+ n step)))
+        n";
+
+    assert_eq!(eval(source), "2");
+}
+
+#[test]
+fn dotimes_runs_body_count_times() {
+    let source = r"
+        (var runs [])
+        (dotimes [i 3] (set runs (list/push runs i)))
+        runs";
+
+    assert_eq!(eval(source), "(0 1 2)");
+}
+
+#[test]
+fn dotimes_with_zero_or_negative_count_never_runs_body() {
+    assert_eq!(
+        output(r#"(dotimes [i 0] (println i)) (println "done")"#),
+        "done\n"
+    );
+    assert_eq!(
+        output(r#"(dotimes [i -2] (println i)) (println "done")"#),
+        "done\n"
+    );
+}
+
+#[test]
+fn dotimes_requires_integer_count() {
+    let error = eval_err("(dotimes [i 1.5] nil)");
+
+    assert!(matches!(error.kind, TaprErrorKind::InvalidArgument { .. }));
+    assert!(error.location.is_some());
+
+    let error = eval_err(r#"(dotimes [i "3"] nil)"#);
+
+    assert!(matches!(error.kind, TaprErrorKind::InvalidArgument { .. }));
+}
+
+#[test]
+fn loop_recur_rebinds_bindings() {
+    let source = "(loop [i 0 acc []] (if (== i 3) acc (recur (+ i 1) (list/push acc i))))";
+
+    assert_eq!(eval(source), "(0 1 2)");
+}
+
+#[test]
+fn recur_with_wrong_number_of_arguments_is_error() {
+    let error =
+        eval_err("(loop [i 0 acc 0] (if (== i 3) acc (recur (+ i 1))))");
+
+    assert!(matches!(error.kind, TaprErrorKind::WrongAmountOfFixedArgs {
+        expected: 2,
+        actual: 1
+    }));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{eval, eval_err, output};
+use tapr::TaprErrorKind;
+
+#[test]
+fn quoted_brackets_are_plain_lists() {
+    assert_eq!(eval("(list/head '[a b])"), "a");
+    assert_eq!(eval("'[a b]"), "(a b)");
+    assert_eq!(eval("(list/head `[a ,(+ 1 1)])"), "a");
+}
+
+#[test]
+fn show_returns_code_as_written() {
+    let source = r"
+        (defmacro show [form] `(quote ,form))
+        (show (defn g [] 1))";
+
+    assert_eq!(eval(source), "(var g (fn () 1))");
+}
+
+#[test]
+fn macro_keeps_floats_and_strings() {
+    let source = r#"
+        (defmacro unless [c & body] `(if ,c nil (do ,@body)))
+        (unless false (println [1 2] 1.0 "tab\there"))"#;
+
+    assert_eq!(output(source), "(1 2)1.0tab\there\n");
+}
+
+#[test]
+fn macro_passes_bindings_through() {
+    let source = r"
+        (defmacro my-let [bindings & body] `(let ,bindings ,@body))
+        (my-let [a 1 b 2] [a b])";
+
+    assert_eq!(eval(source), "(1 2)");
+}
+
+#[test]
+fn macro_defines_typed_function() {
+    let source = r"
+        (defmacro def-adder [name n] `(defn ,name [x:number] (+ x ,n)))
+        (def-adder add5 5)
+        (add5 1)";
+
+    assert_eq!(eval(source), "6");
+}
+
+#[test]
+fn malformed_expansion_is_not_code() {
+    let source = r"
+        (defmacro bad [] `(let x))
+        (bad)";
+
+    let error = eval_err(source);
+
+    assert!(matches!(error.kind, TaprErrorKind::NotCode(_)));
+}
+
+#[test]
+fn auto_gensym_does_not_capture_caller_names() {
+    let source = r"
+        (defmacro swap [a b] `(let [tmp# ,a] (set ,a ,b) (set ,b tmp#)))
+        (var tmp 1)
+        (var other 2)
+        (swap tmp other)
+        [tmp other]";
+
+    assert_eq!(eval(source), "(2 1)");
+}
+
+#[test]
+fn auto_gensym_is_new_for_each_expansion() {
+    let source = r"
+        (defmacro name-of [] `(quote x#))
+        (== (name-of) (name-of))";
+
+    assert_eq!(eval(source), "false");
+}
+
+#[test]
+fn macros_do_not_hide_functions_called_list_or_fstring() {
+    let source = r"
+        (defn list [& xs] :mine)
+        (defmacro m [] `(list 1 2))
+        (m)";
+
+    assert_eq!(eval(source), ":mine");
+
+    let source = r"
+        (defn fstring [& xs] :mine)
+        (defmacro m [] `(fstring 1 2))
+        (m)";
+
+    assert_eq!(eval(source), ":mine");
+}
+
+#[test]
+fn quoted_macro_arguments_keep_brackets_as_plain_lists() {
+    let source = r"
+        (defmacro quoted [x] `(quote ,x))
+        (quoted [1 [2 3]])";
+
+    assert_eq!(eval(source), "(1 (2 3))");
+}
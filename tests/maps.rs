@@ -0,0 +1,58 @@
+mod common;
+
+use common::eval;
+
+#[test]
+fn integer_and_float_are_different_keys() {
+    let source = r"
+        (var m {1 :int 1.0 :float})
+        [(map/get m 1) (map/get m 1.0) (map/keys m)]";
+
+    assert_eq!(eval(source), "(:int :float (1 1.0))");
+}
+
+#[test]
+fn float_keys_are_found_by_bits() {
+    assert_eq!(eval("(map/get {0.1 :a} 0.1)"), ":a");
+    assert_eq!(eval("(map/get {0.0 :zero} -0.0)"), "nil");
+}
+
+#[test]
+fn list_keys_compare_items_strictly() {
+    assert_eq!(eval("(map/get {[1 2] :a} [1 2])"), ":a");
+    assert_eq!(eval("(map/get {[1 2] :a} [1 2.0])"), "nil");
+}
+
+#[test]
+fn numeric_equality_is_separate_from_keys() {
+    assert_eq!(eval("(== 1 1.0)"), "true");
+}
+
+#[test]
+fn keys_keep_insertion_order() {
+    let source = r"
+        (var m (map/assoc {:b 1 :a 2 :c 3} :a 4))
+        (map/keys (map/dissoc m :b))";
+
+    assert_eq!(eval(source), "(:a :c)");
+}
+
+#[test]
+fn function_keys_are_found_by_identity() {
+    let source = r"
+        (defn f [] 1)
+        (defn g [] 1)
+        (var m (map/assoc {} f :f))
+        [(map/get m f) (map/get m g)]";
+
+    assert_eq!(eval(source), "(:f nil)");
+}
+
+#[test]
+fn map_keys_ignore_entry_order() {
+    let source = r"
+        (var m (map/assoc {} {:a 1 :b 2} :found))
+        [(map/get m {:b 2 :a 1}) (map/get m {:a 1 :b 2.0})]";
+
+    assert_eq!(eval(source), "(:found nil)");
+}
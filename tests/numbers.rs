@@ -0,0 +1,110 @@
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use common::{eval, eval_err, interpreter};
+use tapr::{TaprErrorKind, TaprResult, Value};
+
+/// Calls `/` with `values`. It can't be called from source, since `/` doesn't
+/// parse as a symbol.
+fn divide(values: &[i64]) -> TaprResult<Value> {
+    let mut output = Vec::new();
+    let arguments = values.iter().copied().map(Value::Integer).collect();
+
+    let mut intp = interpreter(&mut output);
+
+    intp.call_by_name("/", arguments)
+}
+
+#[test]
+fn integer_literal_out_of_range_is_parse_error() {
+    let error = eval_err("(+ 1 99999999999999999999)");
+
+    assert!(matches!(
+        error.kind,
+        TaprErrorKind::IntegerLiteralOutOfRange(ref literal)
+            if literal == "99999999999999999999"
+    ));
+
+    let location = error.location.expect("Error should have a location.");
+    assert_eq!((location.line_no(), location.col_no()), (1, 6));
+}
+
+#[test]
+fn integer_literal_limits_are_integers() {
+    assert_eq!(eval("-9223372036854775808"), "-9223372036854775808");
+    assert_eq!(eval("9223372036854775807"), "9223372036854775807");
+}
+
+#[test]
+fn int_truncates_towards_zero() {
+    assert_eq!(eval("[(number/int 2.9) (number/int -2.9)]"), "(2 -2)");
+}
+
+#[test]
+fn int_rejects_floats_out_of_range() {
+    for source in ["(number/int 1e19)", "(number/int -1e19)"] {
+        assert!(matches!(
+            eval_err(source).kind,
+            TaprErrorKind::InvalidConversion { target: "int", .. }
+        ));
+    }
+}
+
+#[test]
+fn integer_arithmetic_stays_integer() {
+    let source = "[(+ 1 2) (- 1 2) (* 2 3) (% 7 2) (++ 1) (-- 1)]";
+
+    assert_eq!(eval(source), "(3 -1 6 1 2 0)");
+    assert_eq!(divide(&[6, 3]).unwrap().to_string(), "2");
+}
+
+#[test]
+fn floats_promote_integers() {
+    let source = "[(+ 1 2.0) (- 1.5 1) (* 2 1.5) (% 7.5 2) (++ 1.5)]";
+
+    assert_eq!(eval(source), "(3.0 0.5 3.0 1.5 2.5)");
+}
+
+#[test]
+fn inexact_integer_division_is_float() {
+    assert_eq!(divide(&[7, 2]).unwrap().to_string(), "3.5");
+    assert_eq!(divide(&[1, 4, 2]).unwrap().to_string(), "0.125");
+    assert_eq!(divide(&[12, 3, 2]).unwrap().to_string(), "2");
+}
+
+#[test]
+fn integer_division_by_zero_is_error() {
+    let error = divide(&[1, 0]).unwrap_err();
+    assert!(matches!(error.kind, TaprErrorKind::DivisionByZero(_)));
+
+    let error = eval_err("(% 1 0)");
+    assert!(matches!(error.kind, TaprErrorKind::DivisionByZero(_)));
+}
+
+#[test]
+fn integer_overflow_is_error() {
+    for source in [
+        "(+ 9223372036854775807 1)",
+        "(- -9223372036854775808 1)",
+        "(* 4611686018427387904 2)",
+        "(++ 9223372036854775807)",
+        "(-- -9223372036854775808)",
+        "(% -9223372036854775808 -1)",
+    ] {
+        assert!(
+            matches!(
+                eval_err(source).kind,
+                TaprErrorKind::IntegerOverflow { .. }
+            ),
+            "{source} should overflow"
+        );
+    }
+}
+
+#[test]
+fn overflowing_integer_division_is_float() {
+    let value = divide(&[i64::MIN, -1]).unwrap();
+
+    assert_eq!(value.to_string(), "9223372036854775808.0");
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{eval, eval_err};
+use tapr::TaprErrorKind;
+
+#[test]
+fn legacy_stdlib_keeps_old_names() {
+    let source = r#"
+        (import stdlib :as "")
+        [(is-even 2) (is-odd 2) (map-local (fn [n] (* n 2)) [1 2])]"#;
+
+    assert_eq!(eval(source), "(true false (2 4))");
+}
+
+#[test]
+fn legacy_stdlib_reexports_prelude() {
+    assert_eq!(eval("(import stdlib) (stdlib/sum [1 2 3])"), "6");
+}
+
+#[test]
+fn apply_rejects_macros() {
+    let source = r"
+        (defmacro m [x] x)
+        (apply m [1])";
+
+    assert!(matches!(
+        eval_err(source).kind,
+        TaprErrorKind::MacroNotExpanded(_)
+    ));
+}
+
+#[test]
+fn update_calls_function() {
+    assert_eq!(eval("(map/update {:a 1} :a + 10)"), "{:a 11}");
+}
+
+#[test]
+fn apply_adds_backtrace_frame() {
+    let source = r"
+        (defn fail [] (throw :oops) nil)
+        (apply fail [])";
+
+    let error = eval_err(source);
+    let names =
+        error.backtrace.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+
+    assert!(names.contains(&"fail"), "{names:?}");
+}
+
+#[test]
+fn collections_list_helpers() {
+    let source = r#"
+        (import std/collections :as "c")
+        [(c/range 3) (c/range 2 5) (c/take 2 [1 2 3]) (c/take -1 [1])
+         (c/drop 1 [1 2 3]) (c/drop 5 [1]) (c/concat [1] [] [2 3])
+         (c/reverse [1 2 3]) (c/zip [1 2 3] [:a :b]) (c/nth [1 2] 1)]"#;
+
+    assert_eq!(
+        eval(source),
+        "((0 1 2) (2 3 4) (1 2) () (2 3) () (1 2 3) (3 2 1) ((1 :a) (2 :b)) 2)"
+    );
+}
+
+#[test]
+fn collections_count_and_empty() {
+    let source = r#"
+        (import std/collections :as "c")
+        [(c/count []) (c/count [1 2]) (c/empty? []) (c/empty? [nil])]"#;
+
+    assert_eq!(eval(source), "(0 2 true false)");
+}
+
+#[test]
+fn collections_handle_long_lists() {
+    let source = r#"
+        (import std/collections :as "c")
+        (var values (c/range 100000))
+        [(c/count values) (c/count (c/take 50000 (c/reverse values)))
+         (c/last (c/drop 99999 (c/concat values values)))]"#;
+
+    assert_eq!(eval(source), "(100000 50000 99999)");
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{eval, eval_err};
+use tapr::TaprErrorKind;
+
+#[test]
+fn simple_escapes() {
+    let source = r#"(string/len "\"\\\/\b\f\n\r\t\0")"#;
+
+    assert_eq!(eval(source), "9");
+    assert_eq!(eval(r#""a\tb\nc""#), "a\tb\nc");
+    assert_eq!(eval(r#""say \"hi\"""#), "say \"hi\"");
+}
+
+#[test]
+fn unicode_escapes() {
+    assert_eq!(eval(r#""\u00e9""#), "é");
+    assert_eq!(eval(r#""\u{1F600}""#), "😀");
+    assert_eq!(eval(r#""\u{41}\u0042""#), "AB");
+}
+
+#[test]
+fn surrogate_escape_is_parse_error() {
+    let error = eval_err(r#""\uD800""#);
+
+    assert!(matches!(error.kind, TaprErrorKind::ParserError { .. }));
+}
+
+#[test]
+fn raw_strings_keep_escapes() {
+    assert_eq!(eval(r###"r#"a\nb "c""#"###), r#"a\nb "c""#);
+}
+
+#[test]
+fn long_strings_unescape_and_skip_first_newline() {
+    let source = "\"\"\"\nsay \"hi\"\\t!\"\"\"";
+
+    assert_eq!(eval(source), "say \"hi\"\t!");
+}
+
+#[test]
+fn fstring_text_unescapes() {
+    assert_eq!(eval(r#"(var x 1) f"{{x}}\t{x}""#), "{x}\t1");
+}
+
+#[test]
+fn fstring_ignores_local_str() {
+    let source = r#"(let [str "local" x 1] f"{str}: {x}")"#;
+
+    assert_eq!(eval(source), "local: 1");
+}
+
+#[test]
+fn fstring_passes_through_macros() {
+    let source = r#"
+        (defmacro twice [s] `(str ,s " " ,s))
+        (var x 1)
+        (twice f"x={x}")"#;
+
+    assert_eq!(eval(source), "x=1 x=1");
+}
+
+#[test]
+fn align_pads_with_zeros() {
+    assert_eq!(eval("(number/align 5 42)"), "00042");
+    assert_eq!(eval("(number/align 2 123)"), "123");
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{eval, eval_err};
+use tapr::TaprErrorKind;
+
+#[test]
+fn deep_tail_recursion_runs_in_constant_stack() {
+    let source = r"
+        (defn count-down [n] (if (== n 0) :done (count-down (- n 1))))
+        (count-down 100000)";
+
+    assert_eq!(eval(source), ":done");
+}
+
+#[test]
+fn deep_mutual_tail_recursion_runs_in_constant_stack() {
+    let source = r"
+        (defn is-even [n] (if (== n 0) true (is-odd (- n 1))))
+        (defn is-odd [n] (if (== n 0) false (is-even (- n 1))))
+        (is-even 100000)";
+
+    assert_eq!(eval(source), "true");
+}
+
+#[test]
+fn loop_recur_runs_in_constant_stack() {
+    let source = r"
+        (loop [i 0 acc 0]
+            (if (< i 100000) (recur (+ i 1) (+ acc i)) acc))";
+
+    assert_eq!(eval(source), "4999950000");
+}
+
+#[test]
+fn deep_non_tail_recursion_exceeds_max_call_depth() {
+    let source = r"
+        (defn sum-to [n] (if (== n 0) 0 (+ n (sum-to (- n 1)))))
+        (sum-to 100000)";
+
+    let error = eval_err(source);
+
+    assert!(
+        matches!(error.kind, TaprErrorKind::MaxCallDepth(_)),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn max_call_depth_can_be_caught() {
+    let source = r"
+        (defn sum-to [n] (if (== n 0) 0 (+ n (sum-to (- n 1)))))
+        (try (sum-to 100000) (catch e :caught))";
+
+    assert_eq!(eval(source), ":caught");
+}